@@ -0,0 +1,291 @@
+//! Derive macro for `number2name`; use it through the `derive` feature of that crate.
+
+use ::proc_macro::TokenStream;
+use ::proc_macro2::{Span, TokenStream as TokenStream2};
+use ::quote::quote;
+use ::syn::spanned::Spanned;
+use ::syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr, Type};
+
+/// The built-in charsets of `number2name`, which can be used by name.
+const BUILTINS: &[&str] = &[
+    "HEX",
+    "HEXLOWERCASE",
+    "BASE32",
+    "BASE32LOWERCASE",
+    "BASE32HUMAN",
+    "BASE32CROCKFORD",
+    "BASE32SCNY",
+    "BASE32HEX",
+    "BASE64",
+    "BASE64URL",
+];
+
+/// Write a newtype of an integer as a name, e.g.
+///
+/// ```ignore
+/// #[derive(NameEncoded)]
+/// #[n2n(charset = "BASE32HUMAN")]
+/// struct OrderId(u64);
+/// ```
+///
+/// This generates `Display`, `FromStr`, serde `Serialize` and `Deserialize`, and `to_name`/`from_name`.
+///
+/// Options of `#[n2n(...)]`:
+/// * `charset = "..."`: the name of a built-in charset, or the characters of a charset.
+/// * `case_insensitive`: for a charset of characters, accept both upper and lower case.
+/// * `signed`: encode an unsigned number as the signed number with the same bits, so that
+///   the largest numbers get short names (like negative numbers). This reinterprets the bits
+///   (`u32::MAX` is written like `-1i32`), and is only allowed for unsigned fields.
+#[proc_macro_derive(NameEncoded, attributes(n2n))]
+pub fn derive_name_encoded(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+struct Options {
+    charset: LitStr,
+    case_insensitive: bool,
+    signed: bool,
+}
+
+fn parse_options(input: &DeriveInput) -> Result<Options, Error> {
+    let mut charset = None;
+    let mut case_insensitive = false;
+    let mut signed = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("n2n"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("charset") {
+                charset = Some(meta.value()?.parse::<LitStr>()?);
+            } else if meta.path.is_ident("case_insensitive") {
+                case_insensitive = true;
+            } else if meta.path.is_ident("signed") {
+                signed = true;
+            } else {
+                return Err(meta.error("expected `charset`, `case_insensitive` or `signed`"));
+            }
+            Ok(())
+        })?;
+    }
+    match charset {
+        Some(charset) => Ok(Options {
+            charset,
+            case_insensitive,
+            signed,
+        }),
+        None => Err(Error::new(
+            Span::call_site(),
+            "missing #[n2n(charset = \"...\")] attribute",
+        )),
+    }
+}
+
+/// Check a charset of characters at compile time, like `Charset::try_new` does at runtime.
+fn check_charset(charset: &LitStr, case_insensitive: bool) -> Result<(), Error> {
+    let data = charset.value();
+    if data.is_empty() {
+        return Err(Error::new(charset.span(), "charset cannot be empty"));
+    }
+    let mut seen = Vec::new();
+    for character in data.chars() {
+        let unique = if case_insensitive {
+            let mut lower = character.to_lowercase();
+            match (lower.next(), lower.next()) {
+                (Some(lower), None) => lower,
+                _ => {
+                    return Err(Error::new(
+                        charset.span(),
+                        format!(
+                            "character '{}' has no single-character lower case",
+                            character
+                        ),
+                    ))
+                }
+            }
+        } else {
+            character
+        };
+        if seen.contains(&unique) {
+            return Err(Error::new(
+                charset.span(),
+                format!("charset contains '{}' more than once", character),
+            ));
+        }
+        seen.push(unique);
+    }
+    Ok(())
+}
+
+/// The signed type with the same width, for `signed`.
+fn signed_type(int: &Type) -> Result<TokenStream2, Error> {
+    let name = match int {
+        Type::Path(path) => path.path.get_ident().map(|ident| ident.to_string()),
+        _ => None,
+    };
+    Ok(match name.as_deref() {
+        Some("u8") => quote!(i8),
+        Some("u16") => quote!(i16),
+        Some("u32") => quote!(i32),
+        Some("u64") => quote!(i64),
+        Some("u128") => quote!(i128),
+        Some("usize") => quote!(isize),
+        _ => {
+            return Err(Error::new(
+                int.span(),
+                "`signed` needs an unsigned primitive integer, like u64",
+            ))
+        }
+    })
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let options = parse_options(input)?;
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "NameEncoded does not support generic structs",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "NameEncoded only works for structs",
+            ))
+        }
+    };
+    let field = match fields.iter().collect::<Vec<_>>()[..] {
+        [field] => field,
+        _ => {
+            return Err(Error::new(
+                fields.span(),
+                "NameEncoded needs a struct with exactly one field",
+            ))
+        }
+    };
+    let int = &field.ty;
+    let (get, wrap) = match (&fields, &field.ident) {
+        (Fields::Named(_), Some(name)) => {
+            (quote!(self.#name), quote!(|number| Self { #name: number }))
+        }
+        _ => (quote!(self.0), quote!(Self)),
+    };
+
+    let builtin = BUILTINS.contains(&options.charset.value().as_str());
+    let charset = if builtin {
+        let name = ::syn::Ident::new(&options.charset.value(), options.charset.span());
+        quote!(&::number2name::#name)
+    } else {
+        check_charset(&options.charset, options.case_insensitive)?;
+        let data = &options.charset;
+        let create = if options.case_insensitive {
+            quote!(case_insensitive)
+        } else {
+            quote!(case_sensitive)
+        };
+        quote!({
+            static CHARSET: ::number2name::__private::OnceLock<::number2name::Charset> =
+                ::number2name::__private::OnceLock::new();
+            CHARSET.get_or_init(|| ::number2name::Charset::#create(#data))
+        })
+    };
+    let (to_encoded, from_encoded) = if options.signed {
+        let signed = signed_type(int)?;
+        (
+            quote!((#get as #signed)),
+            quote!(|number: #signed| number as #int),
+        )
+    } else {
+        (quote!(#get), quote!(|number: #int| number))
+    };
+
+    let name = &input.ident;
+    Ok(quote! {
+        impl #name {
+            fn __n2n_charset() -> &'static ::number2name::Charset {
+                #charset
+            }
+
+            /// The name of this number.
+            pub fn to_name(&self) -> ::std::string::String {
+                Self::__n2n_charset().encode_int(#to_encoded)
+            }
+
+            /// Decode a name created by `to_name`.
+            pub fn from_name(text: &str) -> ::core::result::Result<Self, ::number2name::N2NErr> {
+                let number = Self::__n2n_charset().decode_int(text)?;
+                ::core::result::Result::Ok((#wrap)((#from_encoded)(number)))
+            }
+        }
+
+        impl ::core::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                Self::__n2n_charset().encode_into(#to_encoded, f)
+            }
+        }
+
+        impl ::core::str::FromStr for #name {
+            type Err = ::number2name::N2NErr;
+
+            fn from_str(text: &str) -> ::core::result::Result<Self, Self::Err> {
+                Self::from_name(text)
+            }
+        }
+
+        impl ::number2name::__private::serde::Serialize for #name {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: ::number2name::__private::serde::Serializer,
+            {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> ::number2name::__private::serde::Deserialize<'de> for #name {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: ::number2name::__private::serde::Deserializer<'de>,
+            {
+                ::number2name::__private::deserialize_name(deserializer, Self::from_name)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(data: &str, case_insensitive: bool) -> Result<(), String> {
+        check_charset(&LitStr::new(data, Span::call_site()), case_insensitive)
+            .map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn valid_charsets() {
+        assert!(check("abc", false).is_ok());
+        assert!(check("aA", false).is_ok());
+        assert!(check("一二三", true).is_ok());
+    }
+
+    #[test]
+    fn invalid_charsets() {
+        assert_eq!(check("", false).unwrap_err(), "charset cannot be empty");
+        assert_eq!(
+            check("abca", false).unwrap_err(),
+            "charset contains 'a' more than once"
+        );
+        assert_eq!(
+            check("aA", true).unwrap_err(),
+            "charset contains 'A' more than once"
+        );
+        assert!(check("İ", true).is_err());
+    }
+}
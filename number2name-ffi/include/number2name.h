@@ -0,0 +1,157 @@
+/* C interface for number2name. Generated by cbindgen from number2name-ffi/src/lib.rs, do not edit. */
+
+#ifndef NUMBER2NAME_H
+#define NUMBER2NAME_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The result of functions that can fail. Each kind of `N2NErr` has a variant, and `BufferTooSmall`,
+ * `NullPointer` and `InvalidUtf8` are only for this interface.
+ */
+typedef enum N2NStatus {
+  N2N_STATUS_OK = 0,
+  N2N_STATUS_EMPTY_INPUT = 1,
+  /**
+   * Decoding: the number does not fit in the type. Encoding: the name does not fit in memory.
+   */
+  N2N_STATUS_TOO_LARGE = 2,
+  N2N_STATUS_INVALID_CHARACTER = 3,
+  N2N_STATUS_INVALID_LENGTH = 4,
+  N2N_STATUS_INVALID_PREFIX = 5,
+  /**
+   * The buffer cannot hold the name and a terminating zero; the needed length is still returned.
+   */
+  N2N_STATUS_BUFFER_TOO_SMALL = 6,
+  N2N_STATUS_NULL_POINTER = 7,
+  N2N_STATUS_INVALID_UTF8 = 8,
+  N2N_STATUS_INVALID_GROUPING = 9,
+  /**
+   * An error that was added to `N2NErr` after this interface.
+   */
+  N2N_STATUS_OTHER = 10,
+} N2NStatus;
+
+/**
+ * A charset, from `n2n_charset_builtin` or `n2n_charset_new`, which must be freed with `n2n_charset_free`.
+ */
+typedef struct N2NCharset N2NCharset;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * The message of the last failure on this thread, or null if nothing failed yet.
+ * The text stays valid until the next failure on the same thread.
+ */
+const char *n2n_last_error(void);
+
+/**
+ * The built-in charset with this name (ignoring case), like "BASE32HUMAN", or null if there is none.
+ *
+ * # Safety
+ * The name must be null or a zero-terminated string.
+ */
+struct N2NCharset *n2n_charset_builtin(const char *name);
+
+/**
+ * A charset of these UTF-8 characters, or null if there are none, there are duplicates, or (if
+ * case-insensitive) a lower-case is not one character.
+ *
+ * # Safety
+ * The characters must be null or a zero-terminated string.
+ */
+struct N2NCharset *n2n_charset_new(const char *chars, bool case_insensitive);
+
+/**
+ * Free a charset. Does nothing for null.
+ *
+ * # Safety
+ * The charset must be null or come from `n2n_charset_builtin` or `n2n_charset_new`, and not be freed before.
+ */
+void n2n_charset_free(struct N2NCharset *charset);
+
+/**
+ * Write the name of the number into the buffer of `capacity` bytes, as UTF-8 with a terminating zero.
+ * The length of the name (without the zero) is stored in `length`, also if the buffer is too small,
+ * so the buffer can be null with a capacity of zero to find the length. Only a single-character charset
+ * has names too long to fit in memory, which give `TooLarge` without a length.
+ *
+ * # Safety
+ * The charset and length must be valid pointers, and the buffer must have `capacity` bytes.
+ */
+enum N2NStatus n2n_encode_u64(const struct N2NCharset *charset,
+                              uint64_t number,
+                              char *buffer,
+                              size_t capacity,
+                              size_t *length);
+
+/**
+ * Like `n2n_encode_u64`, for signed numbers.
+ *
+ * # Safety
+ * See `n2n_encode_u64`.
+ */
+enum N2NStatus n2n_encode_i64(const struct N2NCharset *charset,
+                              int64_t number,
+                              char *buffer,
+                              size_t capacity,
+                              size_t *length);
+
+/**
+ * Like `n2n_encode_u64`, for the 128-bit number `high * 2^64 + low`.
+ *
+ * # Safety
+ * See `n2n_encode_u64`.
+ */
+enum N2NStatus n2n_encode_u128(const struct N2NCharset *charset,
+                               uint64_t high,
+                               uint64_t low,
+                               char *buffer,
+                               size_t capacity,
+                               size_t *length);
+
+/**
+ * Decode the name of `name_length` bytes of UTF-8 into `number`.
+ *
+ * # Safety
+ * The charset and number must be valid pointers, and the name must have `name_length` bytes.
+ */
+enum N2NStatus n2n_decode_u64(const struct N2NCharset *charset,
+                              const char *name,
+                              size_t name_length,
+                              uint64_t *number);
+
+/**
+ * Like `n2n_decode_u64`, for signed numbers.
+ *
+ * # Safety
+ * See `n2n_decode_u64`.
+ */
+enum N2NStatus n2n_decode_i64(const struct N2NCharset *charset,
+                              const char *name,
+                              size_t name_length,
+                              int64_t *number);
+
+/**
+ * Like `n2n_decode_u64`, for 128-bit numbers, which are split into `high * 2^64 + low`.
+ *
+ * # Safety
+ * See `n2n_decode_u64`; `high` and `low` must be valid pointers.
+ */
+enum N2NStatus n2n_decode_u128(const struct N2NCharset *charset,
+                               const char *name,
+                               size_t name_length,
+                               uint64_t *high,
+                               uint64_t *low);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* NUMBER2NAME_H */
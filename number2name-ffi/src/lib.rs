@@ -0,0 +1,496 @@
+//! C interface for number2name, for encoding the same way from C, C++, Go and others.
+//!
+//! The header is `include/number2name.h`, generated by cbindgen. Functions that can fail return
+//! an `N2NStatus`, and `n2n_last_error` has the message of the last failure on the thread.
+
+use ::std::cell::RefCell;
+use ::std::ffi::{c_char, CStr, CString};
+use ::std::fmt;
+use ::std::ptr;
+use ::std::slice;
+use ::std::str;
+
+use ::number2name::{builtin_charset, Case, Charset, N2NErr, N2NInt};
+
+/// A charset, from `n2n_charset_builtin` or `n2n_charset_new`, which must be freed with `n2n_charset_free`.
+pub struct N2NCharset(Charset);
+
+/// The result of functions that can fail. Each kind of `N2NErr` has a variant, and `BufferTooSmall`,
+/// `NullPointer` and `InvalidUtf8` are only for this interface.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum N2NStatus {
+    Ok = 0,
+    EmptyInput = 1,
+    /// Decoding: the number does not fit in the type. Encoding: the name does not fit in memory.
+    TooLarge = 2,
+    InvalidCharacter = 3,
+    InvalidLength = 4,
+    InvalidPrefix = 5,
+    /// The buffer cannot hold the name and a terminating zero; the needed length is still returned.
+    BufferTooSmall = 6,
+    NullPointer = 7,
+    InvalidUtf8 = 8,
+    InvalidGrouping = 9,
+    /// An error that was added to `N2NErr` after this interface.
+    Other = 10,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Remember the message for `n2n_last_error`.
+fn set_error(message: impl Into<String>) {
+    let message = message.into().replace('\0', "\\0");
+    let message = CString::new(message).expect("zero bytes were replaced");
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+fn fail(status: N2NStatus, message: impl Into<String>) -> N2NStatus {
+    set_error(message);
+    status
+}
+
+fn null_pointer(argument: &str) -> N2NStatus {
+    fail(
+        N2NStatus::NullPointer,
+        format!("the {} pointer is null", argument),
+    )
+}
+
+fn decode_error(err: N2NErr) -> N2NStatus {
+    let status = match err {
+        N2NErr::EmptyInput => N2NStatus::EmptyInput,
+        N2NErr::TooLarge { .. } => N2NStatus::TooLarge,
+        N2NErr::InvalidCharacter { .. } => N2NStatus::InvalidCharacter,
+        N2NErr::InvalidLength { .. } => N2NStatus::InvalidLength,
+        N2NErr::InvalidPrefix { .. } => N2NStatus::InvalidPrefix,
+        N2NErr::InvalidGrouping { .. } => N2NStatus::InvalidGrouping,
+        _ => N2NStatus::Other,
+    };
+    fail(status, err.to_string())
+}
+
+/// The text of a zero-terminated C string, or null if there is none (after setting the error).
+unsafe fn c_str<'a>(text: *const c_char, argument: &str) -> Option<&'a str> {
+    if text.is_null() {
+        null_pointer(argument);
+        return None;
+    }
+    match CStr::from_ptr(text).to_str() {
+        Ok(text) => Some(text),
+        Err(err) => {
+            set_error(format!("the {} is not valid UTF-8: {}", argument, err));
+            None
+        }
+    }
+}
+
+/// The message of the last failure on this thread, or null if nothing failed yet.
+/// The text stays valid until the next failure on the same thread.
+#[no_mangle]
+pub extern "C" fn n2n_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |err| err.as_ptr())
+    })
+}
+
+/// The built-in charset with this name (ignoring case), like "BASE32HUMAN", or null if there is none.
+///
+/// # Safety
+/// The name must be null or a zero-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn n2n_charset_builtin(name: *const c_char) -> *mut N2NCharset {
+    let Some(name) = c_str(name, "charset name") else {
+        return ptr::null_mut();
+    };
+    match builtin_charset(name) {
+        Some(charset) => Box::into_raw(Box::new(N2NCharset(charset.clone()))),
+        None => {
+            set_error(format!("there is no built-in charset named '{}'", name));
+            ptr::null_mut()
+        }
+    }
+}
+
+/// A charset of these UTF-8 characters, or null if there are none, there are duplicates, or (if
+/// case-insensitive) a lower-case is not one character.
+///
+/// # Safety
+/// The characters must be null or a zero-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn n2n_charset_new(
+    chars: *const c_char,
+    case_insensitive: bool,
+) -> *mut N2NCharset {
+    let Some(chars) = c_str(chars, "charset characters") else {
+        return ptr::null_mut();
+    };
+    let case = if case_insensitive {
+        Case::Insensitive
+    } else {
+        Case::Sensitive
+    };
+    match Charset::try_new(chars, case) {
+        Some(charset) => Box::into_raw(Box::new(N2NCharset(charset))),
+        None => {
+            set_error(
+                "a charset needs at least one character, no duplicates and, if case-insensitive, \
+                characters whose lower-case is one character",
+            );
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Free a charset. Does nothing for null.
+///
+/// # Safety
+/// The charset must be null or come from `n2n_charset_builtin` or `n2n_charset_new`, and not be freed before.
+#[no_mangle]
+pub unsafe extern "C" fn n2n_charset_free(charset: *mut N2NCharset) {
+    if !charset.is_null() {
+        drop(Box::from_raw(charset));
+    }
+}
+
+/// Counts the bytes of a name, without storing it.
+struct ByteCounter(usize);
+
+impl fmt::Write for ByteCounter {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.0 += text.len();
+        Ok(())
+    }
+}
+
+/// The number of bytes of the name, or None if that does not fit in a usize.
+fn name_len<T: N2NInt>(charset: &Charset, number: T) -> Option<usize> {
+    if charset.len() == 1 {
+        // The character is repeated once more than the number, which is too slow to count.
+        let count = usize::try_from(number.to_encoded()).ok()?.checked_add(1)?;
+        return count.checked_mul(charset[0].len_utf8());
+    }
+    let mut counter = ByteCounter(0);
+    charset
+        .encode_into(number, &mut counter)
+        .expect("counting does not fail");
+    Some(counter.0)
+}
+
+/// Write the name as UTF-8 with a terminating zero, and store its length (without the zero) in `length`.
+unsafe fn encode<T: N2NInt>(
+    charset: *const N2NCharset,
+    number: T,
+    buffer: *mut c_char,
+    capacity: usize,
+    length: *mut usize,
+) -> N2NStatus {
+    let Some(charset) = charset.as_ref() else {
+        return null_pointer("charset");
+    };
+    if length.is_null() {
+        return null_pointer("length");
+    }
+    let Some(name_len) = name_len(&charset.0, number) else {
+        return fail(
+            N2NStatus::TooLarge,
+            "the name has more bytes than fit in memory",
+        );
+    };
+    *length = name_len;
+    if name_len >= capacity {
+        return fail(
+            N2NStatus::BufferTooSmall,
+            format!(
+                "the name needs {} bytes and a terminating zero, but the buffer has {} bytes",
+                name_len, capacity
+            ),
+        );
+    }
+    if buffer.is_null() {
+        return null_pointer("buffer");
+    }
+    let buffer = slice::from_raw_parts_mut(buffer.cast::<u8>(), capacity);
+    let written = charset.0.encode_to_slice(number, buffer);
+    debug_assert_eq!(written, name_len);
+    buffer[written] = 0;
+    N2NStatus::Ok
+}
+
+/// The number of the name of `name_length` bytes of UTF-8 (which need not end with a zero).
+unsafe fn decode<T: N2NInt>(
+    charset: *const N2NCharset,
+    name: *const c_char,
+    name_length: usize,
+) -> Result<T, N2NStatus> {
+    let Some(charset) = charset.as_ref() else {
+        return Err(null_pointer("charset"));
+    };
+    if name.is_null() {
+        return Err(null_pointer("name"));
+    }
+    let name =
+        str::from_utf8(slice::from_raw_parts(name.cast::<u8>(), name_length)).map_err(|err| {
+            fail(
+                N2NStatus::InvalidUtf8,
+                format!("the name is not valid UTF-8: {}", err),
+            )
+        })?;
+    charset.0.decode_int(name).map_err(decode_error)
+}
+
+/// Write the name of the number into the buffer of `capacity` bytes, as UTF-8 with a terminating zero.
+/// The length of the name (without the zero) is stored in `length`, also if the buffer is too small,
+/// so the buffer can be null with a capacity of zero to find the length. Only a single-character charset
+/// has names too long to fit in memory, which give `TooLarge` without a length.
+///
+/// # Safety
+/// The charset and length must be valid pointers, and the buffer must have `capacity` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn n2n_encode_u64(
+    charset: *const N2NCharset,
+    number: u64,
+    buffer: *mut c_char,
+    capacity: usize,
+    length: *mut usize,
+) -> N2NStatus {
+    encode(charset, number, buffer, capacity, length)
+}
+
+/// Like `n2n_encode_u64`, for signed numbers.
+///
+/// # Safety
+/// See `n2n_encode_u64`.
+#[no_mangle]
+pub unsafe extern "C" fn n2n_encode_i64(
+    charset: *const N2NCharset,
+    number: i64,
+    buffer: *mut c_char,
+    capacity: usize,
+    length: *mut usize,
+) -> N2NStatus {
+    encode(charset, number, buffer, capacity, length)
+}
+
+/// Like `n2n_encode_u64`, for the 128-bit number `high * 2^64 + low`.
+///
+/// # Safety
+/// See `n2n_encode_u64`.
+#[no_mangle]
+pub unsafe extern "C" fn n2n_encode_u128(
+    charset: *const N2NCharset,
+    high: u64,
+    low: u64,
+    buffer: *mut c_char,
+    capacity: usize,
+    length: *mut usize,
+) -> N2NStatus {
+    let number = (high as u128) << 64 | low as u128;
+    encode(charset, number, buffer, capacity, length)
+}
+
+/// Decode the name of `name_length` bytes of UTF-8 into `number`.
+///
+/// # Safety
+/// The charset and number must be valid pointers, and the name must have `name_length` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn n2n_decode_u64(
+    charset: *const N2NCharset,
+    name: *const c_char,
+    name_length: usize,
+    number: *mut u64,
+) -> N2NStatus {
+    if number.is_null() {
+        return null_pointer("number");
+    }
+    match decode(charset, name, name_length) {
+        Ok(decoded) => {
+            *number = decoded;
+            N2NStatus::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+/// Like `n2n_decode_u64`, for signed numbers.
+///
+/// # Safety
+/// See `n2n_decode_u64`.
+#[no_mangle]
+pub unsafe extern "C" fn n2n_decode_i64(
+    charset: *const N2NCharset,
+    name: *const c_char,
+    name_length: usize,
+    number: *mut i64,
+) -> N2NStatus {
+    if number.is_null() {
+        return null_pointer("number");
+    }
+    match decode(charset, name, name_length) {
+        Ok(decoded) => {
+            *number = decoded;
+            N2NStatus::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+/// Like `n2n_decode_u64`, for 128-bit numbers, which are split into `high * 2^64 + low`.
+///
+/// # Safety
+/// See `n2n_decode_u64`; `high` and `low` must be valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn n2n_decode_u128(
+    charset: *const N2NCharset,
+    name: *const c_char,
+    name_length: usize,
+    high: *mut u64,
+    low: *mut u64,
+) -> N2NStatus {
+    if high.is_null() || low.is_null() {
+        return null_pointer("number");
+    }
+    match decode::<u128>(charset, name, name_length) {
+        Ok(decoded) => {
+            *high = (decoded >> 64) as u64;
+            *low = decoded as u64;
+            N2NStatus::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_error() -> String {
+        unsafe { CStr::from_ptr(n2n_last_error()) }
+            .to_str()
+            .unwrap()
+            .to_owned()
+    }
+
+    fn encode_u64(charset: *const N2NCharset, number: u64) -> Result<String, N2NStatus> {
+        let mut buffer = [0 as c_char; 32];
+        let mut length = 0;
+        match unsafe { n2n_encode_u64(charset, number, buffer.as_mut_ptr(), 32, &mut length) } {
+            N2NStatus::Ok => {
+                let name = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+                assert_eq!(name.to_bytes().len(), length);
+                Ok(name.to_str().unwrap().to_owned())
+            }
+            status => Err(status),
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let charset = unsafe { n2n_charset_new(c"abc".as_ptr(), true) };
+        assert_eq!(encode_u64(charset, 13).unwrap(), "aab");
+        let mut number = 0;
+        let status = unsafe { n2n_decode_u64(charset, c"AAB".as_ptr(), 3, &mut number) };
+        assert_eq!(status, N2NStatus::Ok);
+        assert_eq!(number, 13);
+        let mut signed = 0;
+        let status = unsafe { n2n_decode_i64(charset, c"aab".as_ptr(), 3, &mut signed) };
+        assert_eq!(status, N2NStatus::Ok);
+        assert_eq!(signed, -7);
+        unsafe { n2n_charset_free(charset) };
+    }
+
+    #[test]
+    fn u128_halves() {
+        let charset = unsafe { n2n_charset_builtin(c"base32human".as_ptr()) };
+        let number = u128::MAX - 5;
+        let mut buffer = [0 as c_char; 32];
+        let mut length = 0;
+        let status = unsafe {
+            n2n_encode_u128(
+                charset,
+                (number >> 64) as u64,
+                number as u64,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut length,
+            )
+        };
+        assert_eq!(status, N2NStatus::Ok);
+        let name = unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_str().unwrap();
+        assert_eq!(name, number2name::BASE32HUMAN.encode_int(number));
+        let (mut high, mut low) = (0, 0);
+        let status =
+            unsafe { n2n_decode_u128(charset, buffer.as_ptr(), length, &mut high, &mut low) };
+        assert_eq!(status, N2NStatus::Ok);
+        assert_eq!((high as u128) << 64 | low as u128, number);
+        unsafe { n2n_charset_free(charset) };
+    }
+
+    #[test]
+    fn buffer_too_small() {
+        let charset = unsafe { n2n_charset_new(c"ab".as_ptr(), false) };
+        let mut length = 0;
+        let status = unsafe { n2n_encode_u64(charset, 1000, ptr::null_mut(), 0, &mut length) };
+        assert_eq!(status, N2NStatus::BufferTooSmall);
+        assert_eq!(length, 9);
+        let mut buffer = [0 as c_char; 9];
+        let status = unsafe { n2n_encode_u64(charset, 1000, buffer.as_mut_ptr(), 9, &mut length) };
+        assert_eq!(status, N2NStatus::BufferTooSmall);
+        assert!(last_error().contains("9 bytes"));
+        unsafe { n2n_charset_free(charset) };
+    }
+
+    #[test]
+    fn single_character() {
+        let charset = unsafe { n2n_charset_new(c"é".as_ptr(), false) };
+        let mut length = 0;
+        let status =
+            unsafe { n2n_encode_u64(charset, u64::MAX / 4, ptr::null_mut(), 0, &mut length) };
+        assert_eq!(status, N2NStatus::BufferTooSmall);
+        assert_eq!(length, 1 << 63);
+        assert_eq!(encode_u64(charset, 2).unwrap(), "ééé");
+        let status = unsafe { n2n_encode_u64(charset, u64::MAX, ptr::null_mut(), 0, &mut length) };
+        assert_eq!(status, N2NStatus::TooLarge);
+        assert!(last_error().contains("memory"));
+        unsafe { n2n_charset_free(charset) };
+    }
+
+    #[test]
+    fn errors() {
+        let charset = unsafe { n2n_charset_new(c"abc".as_ptr(), false) };
+        let mut number = 0u64;
+        let decode = |name: &[u8], number: &mut u64| unsafe {
+            n2n_decode_u64(charset, name.as_ptr().cast(), name.len(), number)
+        };
+        assert_eq!(decode(b"", &mut number), N2NStatus::EmptyInput);
+        assert_eq!(decode(b"abxc", &mut number), N2NStatus::InvalidCharacter);
+        assert!(last_error().contains("'x'"));
+        assert_eq!(decode(&[b'c'; 50], &mut number), N2NStatus::TooLarge);
+        assert_eq!(decode(b"a\xff", &mut number), N2NStatus::InvalidUtf8);
+        assert_eq!(
+            unsafe { n2n_decode_u64(ptr::null(), c"a".as_ptr(), 1, &mut number) },
+            N2NStatus::NullPointer
+        );
+        assert_eq!(encode_u64(ptr::null(), 1), Err(N2NStatus::NullPointer));
+        unsafe { n2n_charset_free(charset) };
+    }
+
+    #[test]
+    fn invalid_charsets() {
+        assert!(unsafe { n2n_charset_builtin(c"base31".as_ptr()) }.is_null());
+        assert!(last_error().contains("base31"));
+        assert!(unsafe { n2n_charset_new(c"aba".as_ptr(), false) }.is_null());
+        assert!(unsafe { n2n_charset_new(c"aA".as_ptr(), true) }.is_null());
+        assert!(unsafe { n2n_charset_new(c"".as_ptr(), false) }.is_null());
+        assert!(unsafe { n2n_charset_new(c"aİ".as_ptr(), true) }.is_null());
+        assert!(unsafe { n2n_charset_new(ptr::null(), false) }.is_null());
+        let charset = unsafe { n2n_charset_new(c"aA".as_ptr(), false) };
+        assert!(!charset.is_null());
+        unsafe { n2n_charset_free(charset) };
+        unsafe { n2n_charset_free(ptr::null_mut()) };
+    }
+}
@@ -0,0 +1,83 @@
+use ::std::env;
+use ::std::fs;
+use ::std::path::{Path, PathBuf};
+use ::std::process::Command;
+
+fn crate_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// The header should be what cbindgen makes of the current code. If not, the test fails with the
+/// differences. Regenerate it with `N2N_REGENERATE_HEADER=1 cargo test -p number2name-ffi --test c`.
+#[test]
+fn header_is_current() {
+    let config = cbindgen::Config::from_file(crate_dir().join("cbindgen.toml")).unwrap();
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(crate_dir().join("src/lib.rs"))
+        .generate()
+        .expect("could not generate the header")
+        .write(&mut generated);
+    let path = crate_dir().join("include/number2name.h");
+    if env::var_os("N2N_REGENERATE_HEADER").is_some() {
+        fs::write(&path, &generated).unwrap();
+        return;
+    }
+    let current = fs::read_to_string(&path).unwrap_or_default();
+    let generated = String::from_utf8(generated).unwrap();
+    if current != generated {
+        let current: Vec<&str> = current.lines().collect();
+        let generated: Vec<&str> = generated.lines().collect();
+        let mut differences = String::new();
+        for line in 0..current.len().max(generated.len()) {
+            let (old, new) = (current.get(line), generated.get(line));
+            if old != new {
+                differences.push_str(&format!(
+                    "line {}:\n- {}\n+ {}\n",
+                    line + 1,
+                    old.unwrap_or(&""),
+                    new.unwrap_or(&"")
+                ));
+            }
+        }
+        panic!(
+            "{} is out of date; regenerate it with `N2N_REGENERATE_HEADER=1 cargo test -p \
+            number2name-ffi --test c`\n{}",
+            path.display(),
+            differences
+        );
+    }
+}
+
+/// The static library, which cargo builds next to the test executable (in `target/<profile>/deps`).
+fn static_library() -> PathBuf {
+    let test_exe = env::current_exe().unwrap();
+    test_exe.parent().unwrap().join("libnumber2name_ffi.a")
+}
+
+/// Compile the C test program with the system C compiler (or `$CC`), link the static library, and run it.
+#[test]
+fn c_program() {
+    let library = static_library();
+    assert!(library.is_file(), "{} not found", library.display());
+    let program = Path::new(env!("CARGO_TARGET_TMPDIR")).join("number2name_c_test");
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_owned());
+    let status = Command::new(compiler)
+        .args(["-std=c99", "-Wall", "-Wextra", "-Werror", "-o"])
+        .arg(&program)
+        .arg(crate_dir().join("tests/c/test.c"))
+        .arg("-I")
+        .arg(crate_dir().join("include"))
+        .arg(&library)
+        .args(["-lpthread", "-ldl", "-lm"])
+        .status()
+        .expect("could not run the C compiler");
+    assert!(status.success(), "compiling the C test failed");
+    let output = Command::new(&program).output().unwrap();
+    assert!(
+        output.status.success(),
+        "the C test failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
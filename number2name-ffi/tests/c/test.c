@@ -0,0 +1,93 @@
+/* Uses the C interface the way C programs would; run by `cargo test` (see tests/c.rs). */
+
+#include <inttypes.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "number2name.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                     \
+    do {                                                                     \
+        if (!(condition)) {                                                  \
+            fprintf(stderr, "%s:%d: failed: %s\n", __FILE__, __LINE__,       \
+                    #condition);                                             \
+            failures++;                                                      \
+        }                                                                    \
+    } while (0)
+
+static void roundtrip(void) {
+    N2NCharset *charset = n2n_charset_builtin("BASE32HUMAN");
+    CHECK(charset != NULL);
+    char name[32];
+    size_t length = 0;
+    CHECK(n2n_encode_u64(charset, 1000000, name, sizeof name, &length) == N2N_STATUS_OK);
+    CHECK(length == strlen(name));
+    uint64_t number = 0;
+    CHECK(n2n_decode_u64(charset, name, length, &number) == N2N_STATUS_OK);
+    CHECK(number == 1000000);
+
+    int64_t negative = 0;
+    CHECK(n2n_encode_i64(charset, -42, name, sizeof name, &length) == N2N_STATUS_OK);
+    CHECK(n2n_decode_i64(charset, name, length, &negative) == N2N_STATUS_OK);
+    CHECK(negative == -42);
+
+    uint64_t high = 0, low = 0;
+    CHECK(n2n_encode_u128(charset, UINT64_MAX, 7, name, sizeof name, &length) == N2N_STATUS_OK);
+    CHECK(length == 26);
+    CHECK(n2n_decode_u128(charset, name, length, &high, &low) == N2N_STATUS_OK);
+    CHECK(high == UINT64_MAX && low == 7);
+    n2n_charset_free(charset);
+}
+
+static void custom_charset(void) {
+    N2NCharset *charset = n2n_charset_new("abc", true);
+    CHECK(charset != NULL);
+    char name[8];
+    size_t length = 0;
+    CHECK(n2n_encode_u64(charset, 13, name, sizeof name, &length) == N2N_STATUS_OK);
+    CHECK(strcmp(name, "aab") == 0);
+    uint64_t number = 0;
+    CHECK(n2n_decode_u64(charset, "AAB", 3, &number) == N2N_STATUS_OK);
+    CHECK(number == 13);
+    n2n_charset_free(charset);
+
+    CHECK(n2n_charset_new("abca", false) == NULL);
+    CHECK(n2n_last_error() != NULL);
+    CHECK(n2n_charset_builtin("BASE99") == NULL);
+    CHECK(strstr(n2n_last_error(), "BASE99") != NULL);
+}
+
+static void errors(void) {
+    N2NCharset *charset = n2n_charset_new("abc", false);
+    uint64_t number = 0;
+    CHECK(n2n_decode_u64(charset, "", 0, &number) == N2N_STATUS_EMPTY_INPUT);
+    CHECK(n2n_decode_u64(charset, "abxc", 4, &number) == N2N_STATUS_INVALID_CHARACTER);
+    CHECK(strstr(n2n_last_error(), "'x'") != NULL);
+    CHECK(n2n_decode_u64(charset, "cccccccccccccccccccccccccccccccccccccccccccc", 44, &number)
+          == N2N_STATUS_TOO_LARGE);
+
+    /* Find the length first, then encode into a buffer that is just large enough. */
+    size_t length = 0;
+    CHECK(n2n_encode_u64(charset, UINT64_MAX, NULL, 0, &length) == N2N_STATUS_BUFFER_TOO_SMALL);
+    char name[42];
+    CHECK(length + 1 == sizeof name);
+    CHECK(n2n_encode_u64(charset, UINT64_MAX, name, length, &length) == N2N_STATUS_BUFFER_TOO_SMALL);
+    CHECK(n2n_encode_u64(charset, UINT64_MAX, name, sizeof name, &length) == N2N_STATUS_OK);
+    CHECK(n2n_decode_u64(charset, name, length, &number) == N2N_STATUS_OK);
+    CHECK(number == UINT64_MAX);
+    n2n_charset_free(charset);
+}
+
+int main(void) {
+    roundtrip();
+    custom_charset();
+    errors();
+    if (failures > 0) {
+        fprintf(stderr, "%d check(s) failed\n", failures);
+        return 1;
+    }
+    printf("all checks passed\n");
+    return 0;
+}
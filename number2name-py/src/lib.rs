@@ -0,0 +1,222 @@
+//! Python bindings for number2name, as the module `number2name`.
+//!
+//! Numbers can be any Python int that fits in a u128 (or i128 when signed). Decoding errors raise
+//! subclasses of `N2NError` that match the variants of `N2NErr`, with the same details as attributes.
+
+use ::pyo3::create_exception;
+use ::pyo3::exceptions::{PyOverflowError, PyValueError};
+use ::pyo3::prelude::*;
+use ::pyo3::types::PyInt;
+use ::pyo3::IntoPyObjectExt;
+
+use ::number2name::{builtin_charset, builtin_charsets, Case, Charset, N2NErr, N2NInt};
+
+create_exception!(
+    number2name,
+    N2NError,
+    PyValueError,
+    "A name could not be decoded."
+);
+create_exception!(number2name, EmptyInputError, N2NError, "The name is empty.");
+create_exception!(
+    number2name,
+    TooLargeError,
+    N2NError,
+    "The number of the name is larger than `max`."
+);
+create_exception!(
+    number2name,
+    InvalidCharacterError,
+    N2NError,
+    "The name has a `character` that is not in the charset, at `position` (in characters)."
+);
+create_exception!(
+    number2name,
+    InvalidLengthError,
+    N2NError,
+    "The name should have `expected` characters instead of `actual`."
+);
+create_exception!(
+    number2name,
+    InvalidGroupingError,
+    N2NError,
+    "A separator of the grouped name is missing or misplaced at `position` (in characters)."
+);
+create_exception!(
+    number2name,
+    InvalidPrefixError,
+    N2NError,
+    "The name does not start with the `expected` prefix."
+);
+
+/// The longest name that is encoded. Only names of a single-character charset can be longer,
+/// which would otherwise use all memory for large numbers.
+const MAX_NAME_LEN: u128 = 1 << 24;
+
+/// The exception for the error, with its details as attributes.
+fn to_py_err(py: Python<'_>, err: N2NErr) -> PyErr {
+    let message = err.to_string();
+    let (py_err, attributes) = match err {
+        N2NErr::EmptyInput => (EmptyInputError::new_err(message), vec![]),
+        N2NErr::TooLarge { max, .. } => (
+            TooLargeError::new_err(message),
+            vec![("max", max.into_py_any(py))],
+        ),
+        N2NErr::InvalidCharacter {
+            character,
+            position,
+            byte_offset,
+            ..
+        } => (
+            InvalidCharacterError::new_err(message),
+            vec![
+                ("character", character.into_py_any(py)),
+                ("position", position.into_py_any(py)),
+                ("byte_offset", byte_offset.into_py_any(py)),
+            ],
+        ),
+        N2NErr::InvalidLength { expected, actual } => (
+            InvalidLengthError::new_err(message),
+            vec![
+                ("expected", expected.into_py_any(py)),
+                ("actual", actual.into_py_any(py)),
+            ],
+        ),
+        N2NErr::InvalidGrouping {
+            position,
+            byte_offset,
+        } => (
+            InvalidGroupingError::new_err(message),
+            vec![
+                ("position", position.into_py_any(py)),
+                ("byte_offset", byte_offset.into_py_any(py)),
+            ],
+        ),
+        N2NErr::InvalidPrefix { expected } => (
+            InvalidPrefixError::new_err(message),
+            vec![("expected", expected.into_py_any(py))],
+        ),
+        _ => (N2NError::new_err(message), vec![]),
+    };
+    for (name, value) in attributes {
+        if let Err(err) = value.and_then(|value| py_err.value(py).setattr(name, value)) {
+            return err;
+        }
+    }
+    py_err
+}
+
+/// A charset, e.g. `Charset("abc", case_insensitive=True)` or a built-in one like `BASE32HUMAN`.
+#[pyclass(name = "Charset", module = "number2name", frozen)]
+struct PyCharset(Charset);
+
+#[pymethods]
+impl PyCharset {
+    /// Raises ValueError if there are no characters, there are duplicates, or (if case-insensitive)
+    /// a lower-case is not one character.
+    #[new]
+    #[pyo3(signature = (chars, case_insensitive = false))]
+    fn new(chars: &str, case_insensitive: bool) -> PyResult<Self> {
+        let case = if case_insensitive {
+            Case::Insensitive
+        } else {
+            Case::Sensitive
+        };
+        Charset::try_new(chars, case).map(PyCharset).ok_or_else(|| {
+            PyValueError::new_err(
+                "a charset needs at least one character, no duplicates and, if case-insensitive, \
+                characters whose lower-case is one character",
+            )
+        })
+    }
+
+    /// The built-in charset with this name (ignoring case), like "BASE32HUMAN".
+    #[staticmethod]
+    fn builtin(name: &str) -> PyResult<Self> {
+        builtin_charset(name)
+            .map(|charset| PyCharset(charset.clone()))
+            .ok_or_else(|| {
+                PyValueError::new_err(format!("there is no built-in charset named '{}'", name))
+            })
+    }
+
+    /// The name of the number. With `signed`, negative numbers are allowed, but names differ from unsigned ones.
+    /// Raises OverflowError for names of a single-character charset that are too long to store.
+    #[pyo3(signature = (number, signed = false))]
+    fn encode(&self, number: &Bound<'_, PyInt>, signed: bool) -> PyResult<String> {
+        if signed {
+            let number: i128 = number.extract().map_err(|_| {
+                PyOverflowError::new_err("signed numbers should be from -2**127 to 2**127-1")
+            })?;
+            self.encode_int(number)
+        } else {
+            let number: u128 = number.extract().map_err(|_| {
+                PyOverflowError::new_err(
+                    "numbers should be from 0 to 2**128-1 (use signed=True for negative numbers)",
+                )
+            })?;
+            self.encode_int(number)
+        }
+    }
+
+    /// The number of the name, which must have been encoded with the same `signed`.
+    #[pyo3(signature = (name, signed = false))]
+    fn decode(&self, py: Python<'_>, name: &str, signed: bool) -> PyResult<Py<PyAny>> {
+        if signed {
+            let number: i128 = self.0.decode_int(name).map_err(|err| to_py_err(py, err))?;
+            number.into_py_any(py)
+        } else {
+            let number: u128 = self.0.decode_int(name).map_err(|err| to_py_err(py, err))?;
+            number.into_py_any(py)
+        }
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Charset{:?}", self.0)
+    }
+}
+
+impl PyCharset {
+    /// Raises OverflowError if the name is longer than `MAX_NAME_LEN`.
+    fn encode_int<T: N2NInt>(&self, number: T) -> PyResult<String> {
+        if self.0.len() == 1 && number.to_encoded() >= MAX_NAME_LEN {
+            return Err(PyOverflowError::new_err(format!(
+                "the name would have more than {} characters, since the charset has a single character",
+                MAX_NAME_LEN
+            )));
+        }
+        Ok(self.0.encode_int(number))
+    }
+}
+
+#[pymodule]
+#[pyo3(name = "number2name")]
+fn number2name_py(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = module.py();
+    module.add_class::<PyCharset>()?;
+    for (name, charset) in builtin_charsets() {
+        module.add(name, PyCharset(charset.clone()))?;
+    }
+    module.add("N2NError", py.get_type::<N2NError>())?;
+    module.add("EmptyInputError", py.get_type::<EmptyInputError>())?;
+    module.add("TooLargeError", py.get_type::<TooLargeError>())?;
+    module.add(
+        "InvalidCharacterError",
+        py.get_type::<InvalidCharacterError>(),
+    )?;
+    module.add("InvalidLengthError", py.get_type::<InvalidLengthError>())?;
+    module.add(
+        "InvalidGroupingError",
+        py.get_type::<InvalidGroupingError>(),
+    )?;
+    module.add("InvalidPrefixError", py.get_type::<InvalidPrefixError>())?;
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use ::std::env;
+use ::std::fs;
+use ::std::path::Path;
+use ::std::process::Command;
+
+/// Run the Python tests with the local interpreter (or `$PYO3_PYTHON`), importing the module that cargo built.
+#[test]
+fn python_tests() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // Cargo builds the library next to the test executable (in `target/<profile>/deps`).
+    let library = env::current_exe()
+        .unwrap()
+        .with_file_name("libnumber2name_py.so");
+    assert!(library.is_file(), "{} not found", library.display());
+    let module_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("python");
+    fs::create_dir_all(&module_dir).unwrap();
+    fs::copy(&library, module_dir.join("number2name.so")).unwrap();
+    let python = env::var("PYO3_PYTHON").unwrap_or_else(|_| "python3".to_owned());
+    let output = Command::new(python)
+        .args(["-m", "unittest", "discover", "-s"])
+        .arg(crate_dir.join("tests"))
+        .env("PYTHONPATH", &module_dir)
+        .output()
+        .expect("could not run Python");
+    assert!(
+        output.status.success(),
+        "the Python tests failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
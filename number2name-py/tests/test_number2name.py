@@ -0,0 +1,92 @@
+"""Tests of the Python module, run by `cargo test` (see tests/python.rs) with the built module on the path."""
+
+import unittest
+
+import number2name
+from number2name import BASE32HUMAN, Charset
+
+
+class EncodeDecode(unittest.TestCase):
+    def test_roundtrip(self):
+        charset = Charset("abc", case_insensitive=True)
+        self.assertEqual(charset.encode(13), "aab")
+        self.assertEqual(charset.decode("AAB"), 13)
+        self.assertEqual(len(charset), 3)
+        self.assertEqual(str(charset), "abc")
+
+    def test_same_as_rust(self):
+        self.assertEqual(BASE32HUMAN.encode(1000000), "8sua")
+        self.assertEqual(BASE32HUMAN.encode(2**128 - 1), "g999999999999999999999999_")
+        for number in [0, 1, 31, 32, 2**64, 2**128 - 1]:
+            self.assertEqual(BASE32HUMAN.decode(BASE32HUMAN.encode(number)), number)
+
+    def test_signed(self):
+        charset = Charset("abc")
+        self.assertEqual(charset.encode(-7, signed=True), "aab")
+        self.assertEqual(charset.decode("aab", signed=True), -7)
+        for number in [-2**127, -1, 0, 2**127 - 1]:
+            self.assertEqual(charset.decode(charset.encode(number, signed=True), signed=True), number)
+
+    def test_out_of_range(self):
+        with self.assertRaises(OverflowError):
+            BASE32HUMAN.encode(2**128)
+        with self.assertRaises(OverflowError):
+            BASE32HUMAN.encode(-1)
+        with self.assertRaises(OverflowError):
+            BASE32HUMAN.encode(2**127, signed=True)
+        with self.assertRaises(TypeError):
+            BASE32HUMAN.encode("1")
+
+    def test_single_character(self):
+        self.assertEqual(Charset("x").encode(2), "xxx")
+        with self.assertRaises(OverflowError):
+            Charset("x").encode(2**127)
+        with self.assertRaises(OverflowError):
+            Charset("x").encode(-2**100, signed=True)
+
+    def test_builtins(self):
+        self.assertEqual(Charset.builtin("base32human").encode(1000000), "8sua")
+        self.assertEqual(number2name.HEX.encode(255), "ef")
+        self.assertEqual(len(number2name.BASE64URL), 64)
+        with self.assertRaises(ValueError):
+            Charset.builtin("BASE99")
+
+
+class Errors(unittest.TestCase):
+    def test_empty(self):
+        with self.assertRaises(number2name.EmptyInputError):
+            BASE32HUMAN.decode("")
+
+    def test_too_large(self):
+        with self.assertRaises(number2name.TooLargeError) as context:
+            Charset("ab").decode("b" * 200)
+        self.assertEqual(context.exception.max, 2**128 - 1)
+        with self.assertRaises(number2name.TooLargeError) as context:
+            Charset("ab").decode("b" * 128, signed=True)
+        self.assertEqual(context.exception.max, 2**128 - 1)
+
+    def test_invalid_character(self):
+        with self.assertRaises(number2name.InvalidCharacterError) as context:
+            Charset("abc").decode("abé!")
+        self.assertEqual(context.exception.character, "é")
+        self.assertEqual(context.exception.position, 2)
+        self.assertEqual(context.exception.byte_offset, 2)
+
+    def test_hierarchy(self):
+        self.assertTrue(issubclass(number2name.InvalidCharacterError, number2name.N2NError))
+        self.assertTrue(issubclass(number2name.N2NError, ValueError))
+        with self.assertRaises(ValueError):
+            BASE32HUMAN.decode("!")
+
+    def test_invalid_charset(self):
+        for chars in ["", "aba"]:
+            with self.assertRaises(ValueError):
+                Charset(chars)
+        with self.assertRaises(ValueError):
+            Charset("aA", case_insensitive=True)
+        with self.assertRaises(ValueError):
+            Charset("aİ", case_insensitive=True)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,248 @@
+//! SQLite functions for number2name, so that queries can encode and decode names:
+//!
+//! * `number2name(number [, charset])`: the name of a non-negative integer.
+//! * `name2number(name [, charset])`: the integer of a name.
+//! * Collations like `number2name_base32human`, which sort names by their number.
+//!
+//! The charset is the name of a built-in charset (ignoring case), or otherwise the characters of a
+//! case-sensitive charset of at least two characters. The default is BASE32HUMAN. NULL gives NULL.
+//!
+//! `register` adds them to a rusqlite connection. With the `loadable-extension` feature, the library
+//! is an SQLite extension instead, e.g. `.load ./libnumber2name_sqlite` in the sqlite3 shell.
+
+use ::std::cmp::Ordering;
+
+use ::rusqlite::functions::{Context, FunctionFlags};
+use ::rusqlite::types::ValueRef;
+use ::rusqlite::{Connection, Error, Result};
+
+use ::number2name::{builtin_charset, builtin_charsets, Case, Charset, BASE32HUMAN};
+
+/// Add the functions and collations to the connection.
+pub fn register(conn: &Connection) -> Result<()> {
+    let flags = FunctionFlags::SQLITE_UTF8
+        | FunctionFlags::SQLITE_DETERMINISTIC
+        | FunctionFlags::SQLITE_INNOCUOUS;
+    for arguments in [1, 2] {
+        conn.create_scalar_function("number2name", arguments, flags, number2name)?;
+        conn.create_scalar_function("name2number", arguments, flags, name2number)?;
+    }
+    for (name, charset) in builtin_charsets() {
+        let collation = format!("number2name_{}", name.to_lowercase());
+        let charset = charset.clone();
+        conn.create_collation(collation.as_str(), move |left, right| {
+            compare(left, right, &charset)
+        })?;
+    }
+    Ok(())
+}
+
+fn user_error(message: impl Into<String>) -> Error {
+    Error::UserFunctionError(message.into().into())
+}
+
+/// The charset of the second argument, which SQLite keeps between calls if it is constant.
+fn charset(ctx: &Context<'_>) -> Result<Charset> {
+    if ctx.len() < 2 {
+        return Ok(BASE32HUMAN.clone());
+    }
+    let charset = ctx.get_or_create_aux(1, |value| {
+        let text = value
+            .as_str()
+            .map_err(|_| "the charset should be text".to_owned())?;
+        if let Some(charset) = builtin_charset(text) {
+            return Ok(charset.clone());
+        }
+        let charset = Charset::try_new(text, Case::Sensitive).ok_or_else(|| {
+            format!(
+                "'{}' is not a built-in charset, and not a charset of unique characters",
+                text
+            )
+        })?;
+        // Names of a single character grow with the number, up to trillions of characters.
+        if charset.len() == 1 {
+            return Err(format!(
+                "'{}' has a single character, but a charset needs at least two",
+                text
+            ));
+        }
+        Ok(charset)
+    })?;
+    Ok(Charset::clone(&charset))
+}
+
+fn number2name(ctx: &Context<'_>) -> Result<Option<String>> {
+    let number = match ctx.get_raw(0) {
+        ValueRef::Null => return Ok(None),
+        ValueRef::Integer(number) => u64::try_from(number)
+            .map_err(|_| user_error("number2name needs a number that is not negative"))?,
+        _ => return Err(user_error("number2name needs an integer")),
+    };
+    Ok(Some(charset(ctx)?.encode(number)))
+}
+
+fn name2number(ctx: &Context<'_>) -> Result<Option<i64>> {
+    let name = match ctx.get_raw(0) {
+        ValueRef::Null => return Ok(None),
+        ValueRef::Text(name) => {
+            ::std::str::from_utf8(name).map_err(|err| Error::UserFunctionError(err.into()))?
+        }
+        _ => return Err(user_error("name2number needs text")),
+    };
+    let charset = charset(ctx)?;
+    let number: u64 = charset
+        .decode(name)
+        .map_err(|err| Error::UserFunctionError(err.into()))?;
+    i64::try_from(number)
+        .map(Some)
+        .map_err(|_| user_error(format!("the number of '{}' is too large for SQLite", name)))
+}
+
+/// The order of the numbers of the names. Names that cannot be decoded come after all others,
+/// ordered by their bytes, so that the order is complete.
+fn compare(left: &str, right: &str, charset: &Charset) -> Ordering {
+    let is_name = |name: &str| {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|character| charset.index_of(character).is_some())
+    };
+    match (is_name(left), is_name(right)) {
+        (true, true) => charset
+            .compare(left, right)
+            .expect("both names have only characters of the charset"),
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => left.cmp(right),
+    }
+}
+
+/// The entry point that SQLite calls when loading the extension.
+///
+/// # Safety
+/// Should only be called by SQLite.
+#[cfg(feature = "loadable-extension")]
+#[no_mangle]
+pub unsafe extern "C" fn sqlite3_extension_init(
+    db: *mut ::rusqlite::ffi::sqlite3,
+    pz_err_msg: *mut *mut ::std::os::raw::c_char,
+    p_api: *mut ::rusqlite::ffi::sqlite3_api_routines,
+) -> ::std::os::raw::c_int {
+    Connection::extension_init2(db, pz_err_msg, p_api, |conn| {
+        register(&conn)?;
+        Ok(false)
+    })
+}
+
+// Connections are only usable inside SQLite when built as an extension (see tests/extension.rs).
+#[cfg(all(test, not(feature = "loadable-extension")))]
+mod tests {
+    use ::number2name::HEX;
+
+    use super::*;
+
+    fn connection() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        register(&conn).unwrap();
+        conn
+    }
+
+    fn query<T: ::rusqlite::types::FromSql>(conn: &Connection, sql: &str) -> Result<T> {
+        conn.query_row(sql, [], |row| row.get(0))
+    }
+
+    #[test]
+    fn roundtrip() {
+        let conn = connection();
+        let name: String = query(&conn, "SELECT number2name(1000000)").unwrap();
+        assert_eq!(name, BASE32HUMAN.encode(1000000u64));
+        let number: i64 = query(&conn, "SELECT name2number(number2name(1000000))").unwrap();
+        assert_eq!(number, 1000000);
+        let name: String = query(&conn, "SELECT number2name(13, 'abc')").unwrap();
+        assert_eq!(name, "aab");
+        let name: String = query(&conn, "SELECT number2name(255, 'hex')").unwrap();
+        assert_eq!(name, "ef");
+        let number: i64 = query(&conn, "SELECT name2number('EF', 'HEX')").unwrap();
+        assert_eq!(number, 255);
+        let max: i64 = query(
+            &conn,
+            "SELECT name2number(number2name(9223372036854775807))",
+        )
+        .unwrap();
+        assert_eq!(max, i64::MAX);
+    }
+
+    #[test]
+    fn table() {
+        let conn = connection();
+        conn.execute_batch(
+            "CREATE TABLE orders (id INTEGER PRIMARY KEY, name TEXT);
+            INSERT INTO orders (id) VALUES (1), (31), (32), (1000), (7);
+            UPDATE orders SET name = number2name(id, 'base32crockford');",
+        )
+        .unwrap();
+        let mut statement = conn
+            .prepare(
+                "SELECT id FROM orders WHERE name2number(name, 'base32crockford') > 10 ORDER BY id",
+            )
+            .unwrap();
+        let ids: Vec<i64> = statement
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(ids, [31, 32, 1000]);
+    }
+
+    #[test]
+    fn null() {
+        let conn = connection();
+        let name: Option<String> = query(&conn, "SELECT number2name(NULL)").unwrap();
+        assert_eq!(name, None);
+        let number: Option<i64> = query(&conn, "SELECT name2number(NULL, 'hex')").unwrap();
+        assert_eq!(number, None);
+    }
+
+    #[test]
+    fn errors() {
+        let conn = connection();
+        let message = |sql: &str| query::<i64>(&conn, sql).unwrap_err().to_string();
+        assert!(message("SELECT number2name(-1)").contains("negative"));
+        assert!(message("SELECT number2name('1')").contains("integer"));
+        assert!(message("SELECT name2number(1)").contains("text"));
+        assert!(message("SELECT name2number('ab!', 'abc')").contains("'!'"));
+        assert!(message("SELECT name2number('', 'abc')").contains("empty"));
+        let too_large = format!("SELECT name2number('{}', 'hex')", HEX.encode(1u64 << 63));
+        assert!(message(&too_large).contains("for SQLite"));
+        assert!(message("SELECT name2number('fffffffffffffffff', 'hex')").contains("too large"));
+        assert!(message("SELECT number2name(1, 'abca')").contains("'abca'"));
+        assert!(message("SELECT number2name(1, 3)").contains("text"));
+        assert!(message("SELECT number2name(9223372036854775807, 'x')").contains("at least two"));
+        assert!(message("SELECT name2number('xx', 'x')").contains("at least two"));
+    }
+
+    #[test]
+    fn collation() {
+        let conn = connection();
+        conn.execute_batch(
+            "CREATE TABLE names (name TEXT COLLATE number2name_base32human);
+            INSERT INTO names VALUES ('b'), ('_'), ('ab'), ('A'), ('ba'), ('!'), (NULL);",
+        )
+        .unwrap();
+        let mut statement = conn
+            .prepare("SELECT name FROM names ORDER BY name")
+            .unwrap();
+        let names: Vec<Option<String>> = statement
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        let names: Vec<&str> = names
+            .iter()
+            .map(|name| name.as_deref().unwrap_or("NULL"))
+            .collect();
+        assert_eq!(names, ["NULL", "A", "b", "_", "ab", "ba", "!"]);
+        let count: i64 = query(&conn, "SELECT count(*) FROM names WHERE name = 'B'").unwrap();
+        assert_eq!(count, 1);
+    }
+}
@@ -0,0 +1,28 @@
+#![cfg(feature = "loadable-extension")]
+
+use ::std::env;
+use ::std::process::Command;
+
+/// Load the extension into the sqlite3 shell (or `$SQLITE3`) and use it on an in-memory database.
+#[test]
+fn load_extension() {
+    // Cargo builds the library next to the test executable (in `target/<profile>/deps`).
+    let library = env::current_exe()
+        .unwrap()
+        .with_file_name("libnumber2name_sqlite.so");
+    assert!(library.is_file(), "{} not found", library.display());
+    let sqlite = env::var("SQLITE3").unwrap_or_else(|_| "sqlite3".to_owned());
+    let output = Command::new(sqlite)
+        .arg(":memory:")
+        .arg(format!(".load {}", library.display()))
+        .arg("SELECT number2name(13, 'abc'), name2number('EF', 'hex');")
+        .arg("SELECT group_concat(name, ' ') FROM (SELECT column1 AS name FROM (VALUES ('ab'), ('_'), ('b')) ORDER BY name COLLATE number2name_base32human);")
+        .output()
+        .expect("could not run sqlite3");
+    assert!(
+        output.status.success(),
+        "sqlite3 failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "aab|255\nb _ ab\n");
+}
@@ -0,0 +1,213 @@
+use ::std::ffi::OsString;
+use ::std::fs;
+use ::std::fs::{File, OpenOptions};
+use ::std::io;
+use ::std::io::Write;
+use ::std::ops::Range;
+use ::std::path::{Path, PathBuf};
+
+use crate::Charset;
+
+/// Hands out sequential names, remembering the next free number in a local state file.
+///
+/// Reservations are atomic across processes: a separate lock file is locked while the
+/// state is updated, and the state file is replaced atomically, so a crash never loses
+/// or repeats a reservation (though a crash after reserving may skip numbers). On platforms
+/// other than Unix, the directory is not synced, so a power loss may still undo the last update.
+#[derive(Debug, Clone)]
+pub struct NameAllocator {
+    path: PathBuf,
+    charset: Charset,
+}
+
+impl NameAllocator {
+    /// The state file (and lock and temporary files next to it) are created when needed.
+    pub fn new(path: impl Into<PathBuf>, charset: Charset) -> Self {
+        NameAllocator {
+            path: path.into(),
+            charset,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn charset(&self) -> &Charset {
+        &self.charset
+    }
+
+    /// The next number that would be reserved, without reserving it.
+    pub fn peek(&self) -> io::Result<u64> {
+        let _lock = self.lock()?;
+        self.read_next()
+    }
+
+    /// Reserve the next `count` numbers.
+    pub fn reserve(&self, count: u64) -> io::Result<Range<u64>> {
+        let _lock = self.lock()?;
+        let first = self.read_next()?;
+        let end = first.checked_add(count).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "cannot reserve {} more numbers after {}; out of numbers",
+                    count, first
+                ),
+            )
+        })?;
+        self.write_next(end)?;
+        Ok(first..end)
+    }
+
+    /// Reserve the next `count` numbers and return their names.
+    pub fn next_names(&self, count: u64) -> io::Result<Vec<String>> {
+        Ok(self
+            .reserve(count)?
+            .map(|number| self.charset.encode_u64(number))
+            .collect())
+    }
+
+    /// Reserve the next number and return its name.
+    pub fn next_name(&self) -> io::Result<String> {
+        let number = self.reserve(1)?.start;
+        Ok(self.charset.encode_u64(number))
+    }
+
+    /// Lock that is held until the returned file is dropped.
+    fn lock(&self) -> io::Result<File> {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.sibling(".lock"))?;
+        file.lock()?;
+        Ok(file)
+    }
+
+    fn read_next(&self) -> io::Result<u64> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(err) => return Err(err),
+        };
+        content.trim().parse().map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "state file '{}' does not contain a valid number",
+                    self.path.display()
+                ),
+            )
+        })
+    }
+
+    /// Write to a temporary file and rename it, so the state file is never partially written.
+    /// The directory is synced after the rename, so that the rename itself survives a crash.
+    fn write_next(&self, next: u64) -> io::Result<()> {
+        let tmp_path = self.sibling(".tmp");
+        let mut tmp = File::create(&tmp_path)?;
+        writeln!(tmp, "{}", next)?;
+        tmp.sync_all()?;
+        fs::rename(&tmp_path, &self.path)?;
+        self.sync_dir()
+    }
+
+    /// Only on Unix, since other platforms cannot open a directory as a file to sync it.
+    #[cfg(unix)]
+    fn sync_dir(&self) -> io::Result<()> {
+        let dir = match self.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        File::open(dir)?.sync_all()
+    }
+
+    #[cfg(not(unix))]
+    fn sync_dir(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn sibling(&self, extension: &str) -> PathBuf {
+        let mut path: OsString = self.path.clone().into_os_string();
+        path.push(extension);
+        PathBuf::from(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::std::thread;
+
+    use ::tempfile::TempDir;
+
+    use super::*;
+
+    /// The state file in a new directory, which is removed when the `TempDir` is dropped.
+    fn state_path() -> (TempDir, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state");
+        (dir, path)
+    }
+
+    #[test]
+    fn sequential() -> io::Result<()> {
+        let (_dir, path) = state_path();
+        let allocator = NameAllocator::new(path, Charset::case_sensitive("abc"));
+        assert_eq!(allocator.peek()?, 0);
+        assert_eq!(allocator.next_name()?, "a");
+        assert_eq!(allocator.next_names(3)?, vec!["b", "c", "aa"]);
+        assert_eq!(allocator.reserve(2)?, 4..6);
+        assert_eq!(allocator.peek()?, 6);
+        Ok(())
+    }
+
+    #[test]
+    fn persists() -> io::Result<()> {
+        let (_dir, path) = state_path();
+        NameAllocator::new(&path, Charset::case_sensitive("abc")).reserve(10)?;
+        let allocator = NameAllocator::new(&path, Charset::case_sensitive("abc"));
+        assert_eq!(allocator.reserve(1)?, 10..11);
+        assert_eq!(fs::read_to_string(&path)?, "11\n");
+        Ok(())
+    }
+
+    #[test]
+    fn corrupted() {
+        let (_dir, path) = state_path();
+        fs::write(&path, "not a number").unwrap();
+        let allocator = NameAllocator::new(&path, Charset::case_sensitive("abc"));
+        let err = allocator.reserve(1).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn exhausted() {
+        let (_dir, path) = state_path();
+        fs::write(&path, format!("{}\n", u64::MAX - 1)).unwrap();
+        let allocator = NameAllocator::new(&path, Charset::case_sensitive("abc"));
+        assert_eq!(allocator.reserve(1).unwrap(), u64::MAX - 1..u64::MAX);
+        assert!(allocator.reserve(1).is_err());
+    }
+
+    #[test]
+    fn concurrent() {
+        let (_dir, path) = state_path();
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let allocator = NameAllocator::new(&path, Charset::case_sensitive("abc"));
+                thread::spawn(move || {
+                    (0..25)
+                        .map(|_| allocator.reserve(2).unwrap().start)
+                        .collect::<Vec<u64>>()
+                })
+            })
+            .collect();
+        let mut starts: Vec<u64> = handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect();
+        starts.sort_unstable();
+        assert_eq!(starts, (0..400).step_by(2).collect::<Vec<u64>>());
+    }
+}
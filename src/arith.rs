@@ -0,0 +1,212 @@
+use ::alloc::string::String;
+use ::alloc::vec::Vec;
+use ::core::cmp::Ordering;
+
+use crate::digits;
+use crate::typ::N2NErr;
+use crate::Charset;
+
+// Operations directly on names, so they work for names of any length,
+// including those too long to decode into a u128.
+
+fn to_digits(text: &str, charset: &Charset) -> Result<Vec<usize>, N2NErr> {
+    if text.is_empty() {
+        return Err(N2NErr::EmptyInput);
+    }
+    text.chars()
+        .map(|character| match charset.index_of(character) {
+            Some(index) => Ok(index as usize),
+            None => Err(N2NErr::invalid_in(text, charset, |_, c| {
+                charset.index_of(c).is_some()
+            })),
+        })
+        .collect()
+}
+
+fn to_name(digits: &[usize], charset: &Charset) -> String {
+    digits.iter().map(|&index| charset[index]).collect()
+}
+
+/// The name of the next number.
+pub fn name_succ(text: impl AsRef<str>, charset: &Charset) -> Result<String, N2NErr> {
+    let mut digits = to_digits(text.as_ref(), charset)?;
+    digits::increment(&mut digits, charset.len());
+    Ok(to_name(&digits, charset))
+}
+
+/// The name of the previous number, or None for the first name.
+pub fn name_pred(text: impl AsRef<str>, charset: &Charset) -> Result<Option<String>, N2NErr> {
+    let mut digits = to_digits(text.as_ref(), charset)?;
+    if !digits::decrement(&mut digits, charset.len()) {
+        return Ok(None);
+    }
+    Ok(Some(to_name(&digits, charset)))
+}
+
+/// The name of the number that is `amount` higher.
+/// Fails with `TooLarge` for a single-character charset if the name does not fit in memory.
+pub fn name_add(text: impl AsRef<str>, amount: u128, charset: &Charset) -> Result<String, N2NErr> {
+    let mut digits = to_digits(text.as_ref(), charset)?;
+    if !digits::add(&mut digits, amount, charset.len()) {
+        return Err(N2NErr::TooLarge {
+            charset: Some(charset.clone()),
+            max: usize::MAX as u128 - 1,
+        });
+    }
+    Ok(to_name(&digits, charset))
+}
+
+/// The name of the number that is `amount` lower, or None if that would be below zero.
+pub fn name_sub(
+    text: impl AsRef<str>,
+    amount: u128,
+    charset: &Charset,
+) -> Result<Option<String>, N2NErr> {
+    let mut digits = to_digits(text.as_ref(), charset)?;
+    if !digits::sub(&mut digits, amount, charset.len()) {
+        return Ok(None);
+    }
+    Ok(Some(to_name(&digits, charset)))
+}
+
+/// The difference between the numbers of two names (regardless of which is larger).
+/// Fails with `TooLarge` if the difference does not fit in a u128.
+pub fn name_distance(
+    left: impl AsRef<str>,
+    right: impl AsRef<str>,
+    charset: &Charset,
+) -> Result<u128, N2NErr> {
+    let left = to_digits(left.as_ref(), charset)?;
+    let right = to_digits(right.as_ref(), charset)?;
+    digits::distance(&left, &right, charset.len()).ok_or_else(|| N2NErr::TooLarge {
+        charset: Some(charset.clone()),
+        max: u128::MAX,
+    })
+}
+
+/// Compare names in the order of their numbers, which is shorter names first,
+/// and same-length names by the position of their characters in the charset.
+pub fn name_cmp(
+    left: impl AsRef<str>,
+    right: impl AsRef<str>,
+    charset: &Charset,
+) -> Result<Ordering, N2NErr> {
+    let left = to_digits(left.as_ref(), charset)?;
+    let right = to_digits(right.as_ref(), charset)?;
+    Ok(digits::cmp(&left, &right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn succ() -> Result<(), N2NErr> {
+        let charset = Charset::case_sensitive("abc");
+        assert_eq!(name_succ("a", &charset)?, "b");
+        assert_eq!(name_succ("c", &charset)?, "aa");
+        assert_eq!(name_succ("acc", &charset)?, "baa");
+        assert_eq!(name_succ("ccc", &charset)?, "aaaa");
+        Ok(())
+    }
+
+    #[test]
+    fn pred() -> Result<(), N2NErr> {
+        let charset = Charset::case_sensitive("abc");
+        assert_eq!(name_pred("b", &charset)?, Some("a".to_owned()));
+        assert_eq!(name_pred("aa", &charset)?, Some("c".to_owned()));
+        assert_eq!(name_pred("baa", &charset)?, Some("acc".to_owned()));
+        assert_eq!(name_pred("a", &charset)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn case_insensitive() -> Result<(), N2NErr> {
+        let charset = Charset::case_insensitive("aBc");
+        assert_eq!(name_succ("Ab", &charset)?, "ac");
+        assert_eq!(name_cmp("AB", "ab", &charset)?, Ordering::Equal);
+        Ok(())
+    }
+
+    #[test]
+    fn add_sub() -> Result<(), N2NErr> {
+        let charset = Charset::case_sensitive("abcdefghijklmnopqrstuvwxyz");
+        let start = charset.encode_u128(1_000_000);
+        assert_eq!(
+            name_add(&start, 123_456_789, &charset)?,
+            charset.encode_u128(124_456_789)
+        );
+        assert_eq!(
+            name_sub(&start, 999_999, &charset)?,
+            Some(charset.encode_u128(1))
+        );
+        assert_eq!(name_sub(&start, 1_000_001, &charset)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn beyond_u128() -> Result<(), N2NErr> {
+        let charset = Charset::case_sensitive("0123456789");
+        let long = "9".repeat(60);
+        let next = name_succ(&long, &charset)?;
+        assert_eq!(next, "0".repeat(61));
+        assert_eq!(
+            name_add(&long, 5, &charset)?,
+            format!("{}4", "0".repeat(60))
+        );
+        assert_eq!(name_distance(&long, &next, &charset)?, 1);
+        assert_eq!(name_cmp(&long, &next, &charset)?, Ordering::Less);
+        assert_eq!(name_pred(&next, &charset)?, Some(long));
+        Ok(())
+    }
+
+    #[test]
+    fn single_character_too_long() {
+        let charset = Charset::case_sensitive("x");
+        match name_add("xx", u128::MAX, &charset).unwrap_err() {
+            N2NErr::TooLarge { .. } => {}
+            _ => panic!("wrong error"),
+        }
+    }
+
+    #[test]
+    fn distance() -> Result<(), N2NErr> {
+        let charset = Charset::case_sensitive("abc");
+        assert_eq!(name_distance("a", "aab", &charset)?, 13);
+        assert_eq!(name_distance("aab", "a", &charset)?, 13);
+        assert_eq!(name_distance("bca", "bca", &charset)?, 0);
+        match name_distance("a", "a".repeat(90), &charset).unwrap_err() {
+            N2NErr::TooLarge { .. } => {}
+            _ => panic!("wrong error"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn compare() -> Result<(), N2NErr> {
+        let charset = Charset::case_sensitive("cba");
+        assert_eq!(name_cmp("a", "cc", &charset)?, Ordering::Less);
+        assert_eq!(name_cmp("cb", "ca", &charset)?, Ordering::Less);
+        assert_eq!(name_cmp("ac", "bc", &charset)?, Ordering::Greater);
+        let mut names: Vec<String> = (0u64..30).rev().map(|nr| charset.encode(nr)).collect();
+        names.sort_by(|left, right| name_cmp(left, right, &charset).unwrap());
+        assert_eq!(
+            names,
+            (0u64..30).map(|nr| charset.encode(nr)).collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid() {
+        let charset = Charset::case_sensitive("abc");
+        match name_succ("abd", &charset).unwrap_err() {
+            N2NErr::InvalidCharacter { character, .. } => assert_eq!(character, 'd'),
+            _ => panic!("wrong error"),
+        }
+        match name_cmp("a", "", &charset).unwrap_err() {
+            N2NErr::EmptyInput => {}
+            _ => panic!("wrong error"),
+        }
+    }
+}
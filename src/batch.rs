@@ -0,0 +1,315 @@
+use ::alloc::string::String;
+use ::alloc::vec::Vec;
+
+use crate::decode::{append_digit_u64, name2number_u64};
+use crate::encode::number2name_into_u64;
+use crate::typ::N2NErr;
+use crate::Charset;
+
+/// Numbers encoded together in `encode_batch`, so that their divisions can overlap.
+const LANES: usize = 4;
+
+/// Longest name of a u64 for charsets of at least two characters.
+const MAX_LEN: usize = u64::BITS as usize;
+
+/// Marks ASCII characters that are not in the charset.
+const NOT_IN_CHARSET: u8 = u8::MAX;
+
+/// Byte lookups for charsets that only have ASCII characters.
+#[derive(Debug)]
+pub(crate) struct AsciiTable {
+    chars: Vec<u8>,
+    /// The index of each ASCII character, or `NOT_IN_CHARSET`.
+    index: [u8; 128],
+}
+
+impl AsciiTable {
+    /// The table for the charset, if all its characters are ASCII.
+    pub(crate) fn for_charset(charset: &Charset) -> Option<Self> {
+        let chars = (0..charset.len())
+            .map(|index| u8::try_from(charset[index]).ok().filter(u8::is_ascii))
+            .collect::<Option<Vec<u8>>>()?;
+        let mut index = [NOT_IN_CHARSET; 128];
+        for (byte, slot) in (0u8..128).zip(index.iter_mut()) {
+            if let Some(position) = charset.index_of(char::from(byte)) {
+                *slot = position as u8;
+            }
+        }
+        Some(AsciiTable { chars, index })
+    }
+}
+
+/// Append the names of all numbers to `out` as UTF-8, with the separator between them.
+/// Panics if the separator is in the charset, since the names could then not be separated again.
+pub fn encode_batch(numbers: &[u64], charset: &Charset, out: &mut Vec<u8>, separator: char) {
+    assert!(
+        charset.index_of(separator).is_none(),
+        "the separator cannot be a character of the charset"
+    );
+    let mut separator_bytes = [0u8; 4];
+    let separator = separator.encode_utf8(&mut separator_bytes).as_bytes();
+    let table = match charset.ascii() {
+        Some(table) if charset.len() > 1 => table,
+        _ => {
+            let mut name = String::new();
+            for (index, &number) in numbers.iter().enumerate() {
+                if index > 0 {
+                    out.extend_from_slice(separator);
+                }
+                name.clear();
+                number2name_into_u64(number, charset, &mut name)
+                    .expect("writing to a String does not fail");
+                out.extend_from_slice(name.as_bytes());
+            }
+            return;
+        }
+    };
+    let divisor = charset.divisor();
+    out.reserve(numbers.len() * (charset.max_len_for::<u64>() + separator.len()));
+    let mut chunks = numbers.chunks_exact(LANES);
+    let mut first = true;
+    for chunk in &mut chunks {
+        let mut remainders = [chunk[0], chunk[1], chunk[2], chunk[3]];
+        let mut names = [[0u8; MAX_LEN]; LANES];
+        let mut starts = [MAX_LEN; LANES];
+        let mut done = [false; LANES];
+        // The numbers are independent, so the processor can work on all lanes at once.
+        while done.contains(&false) {
+            for lane in 0..LANES {
+                if done[lane] {
+                    continue;
+                }
+                let (quotient, digit) = divisor.div_rem(remainders[lane]);
+                starts[lane] -= 1;
+                names[lane][starts[lane]] = table.chars[digit as usize];
+                if quotient == 0 {
+                    done[lane] = true;
+                } else {
+                    remainders[lane] = quotient - 1;
+                }
+            }
+        }
+        for lane in 0..LANES {
+            if !first {
+                out.extend_from_slice(separator);
+            }
+            first = false;
+            out.extend_from_slice(&names[lane][starts[lane]..]);
+        }
+    }
+    for &number in chunks.remainder() {
+        if !first {
+            out.extend_from_slice(separator);
+        }
+        first = false;
+        let mut remainder = number;
+        let mut name = [0u8; MAX_LEN];
+        let mut start = MAX_LEN;
+        loop {
+            let (quotient, digit) = divisor.div_rem(remainder);
+            start -= 1;
+            name[start] = table.chars[digit as usize];
+            if quotient == 0 {
+                break;
+            }
+            remainder = quotient - 1;
+        }
+        out.extend_from_slice(&name[start..]);
+    }
+}
+
+/// Decode names separated by the separator (as made by `encode_batch`), appending the numbers to `out`.
+///
+/// Empty text has no names. On failure, `out` has the numbers of the names before the failing one,
+/// and error positions are within the whole text.
+pub fn decode_batch(
+    text: &str,
+    charset: &Charset,
+    separator: char,
+    out: &mut Vec<u64>,
+) -> Result<(), N2NErr> {
+    assert!(
+        charset.index_of(separator).is_none(),
+        "the separator cannot be a character of the charset"
+    );
+    if text.is_empty() {
+        return Ok(());
+    }
+    let table = match charset.ascii() {
+        Some(table) if separator.is_ascii() => table,
+        _ => return decode_split(text, 0, charset, separator, out),
+    };
+    let size = table.chars.len() as u64;
+    let separator_byte = separator as u8;
+    let mut start = 0;
+    let mut number = None;
+    for (offset, &byte) in text.as_bytes().iter().enumerate() {
+        if byte == separator_byte {
+            match number {
+                Some(number) => out.push(number),
+                None => return decode_split(text, start, charset, separator, out),
+            }
+            number = None;
+            start = offset + 1;
+            continue;
+        }
+        // Non-ASCII characters may still match through case folding, so any name that the
+        // table cannot decode goes to the slow path, which also reports the error.
+        number = match table.index.get(byte as usize) {
+            Some(&digit) if digit != NOT_IN_CHARSET => append_digit_u64(number, digit as u64, size),
+            _ => None,
+        };
+        if number.is_none() {
+            return decode_split(text, start, charset, separator, out);
+        }
+    }
+    match number {
+        Some(number) => out.push(number),
+        None => return decode_split(text, start, charset, separator, out),
+    }
+    Ok(())
+}
+
+/// Decode the names from this byte on one at a time, without the ASCII table.
+fn decode_split(
+    text: &str,
+    mut start: usize,
+    charset: &Charset,
+    separator: char,
+    out: &mut Vec<u64>,
+) -> Result<(), N2NErr> {
+    for name in text[start..].split(separator) {
+        match name2number_u64(name, charset) {
+            Ok(number) => out.push(number),
+            Err(err) => return Err(batch_error(text, start, err)),
+        }
+        start += name.len() + separator.len_utf8();
+    }
+    Ok(())
+}
+
+/// Make error positions relative to the whole text, instead of the name starting at this byte.
+fn batch_error(text: &str, start: usize, err: N2NErr) -> N2NErr {
+    err.with_offset(text[..start].chars().count(), start)
+}
+
+#[cfg(test)]
+mod tests {
+    use ::alloc::vec;
+
+    use super::*;
+
+    fn encode(numbers: &[u64], charset: &Charset, separator: char) -> String {
+        let mut out = Vec::new();
+        encode_batch(numbers, charset, &mut out, separator);
+        String::from_utf8(out).unwrap()
+    }
+
+    fn decode(text: &str, charset: &Charset, separator: char) -> Result<Vec<u64>, N2NErr> {
+        let mut out = Vec::new();
+        decode_batch(text, charset, separator, &mut out)?;
+        Ok(out)
+    }
+
+    #[test]
+    fn same_as_single() -> Result<(), N2NErr> {
+        let numbers: Vec<u64> = (0..50).chain([u64::MAX - 1, u64::MAX]).collect();
+        for charset in [
+            Charset::case_insensitive("aBcD"),
+            Charset::case_sensitive("一二三"),
+            Charset::case_sensitive("x"),
+        ] {
+            for separator in [',', '\n', '€'] {
+                let count = if charset.len() == 1 { 5 } else { numbers.len() };
+                let numbers = &numbers[..count];
+                let text = encode(numbers, &charset, separator);
+                let expected: Vec<String> = numbers.iter().map(|&nr| charset.encode(nr)).collect();
+                assert_eq!(text, expected.join(&String::from(separator)));
+                assert_eq!(decode(&text, &charset, separator)?, numbers);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn empty() -> Result<(), N2NErr> {
+        let charset = Charset::case_sensitive("abc");
+        assert_eq!(encode(&[], &charset, ','), "");
+        assert_eq!(decode("", &charset, ',')?, Vec::<u64>::new());
+        assert_eq!(encode(&[0], &charset, ','), "a");
+        Ok(())
+    }
+
+    #[test]
+    fn appends() -> Result<(), N2NErr> {
+        let charset = Charset::case_sensitive("abc");
+        let mut out = b"names: ".to_vec();
+        encode_batch(&[1, 2], &charset, &mut out, ' ');
+        assert_eq!(out, b"names: b c");
+        let mut numbers = vec![7];
+        decode_batch("b c", &charset, ' ', &mut numbers)?;
+        assert_eq!(numbers, [7, 1, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn first_error() {
+        for charset in [
+            Charset::case_sensitive("abc"),
+            Charset::case_sensitive("abcé"),
+        ] {
+            let mut out = Vec::new();
+            match decode_batch("ab,ba,cxd,?", &charset, ',', &mut out).unwrap_err() {
+                N2NErr::InvalidCharacter {
+                    character,
+                    position,
+                    byte_offset,
+                    more,
+                    ..
+                } => {
+                    assert_eq!(character, 'x');
+                    assert_eq!(position, 7);
+                    assert_eq!(byte_offset, 7);
+                    assert_eq!(more[0].position, 8);
+                }
+                _ => panic!("wrong error"),
+            }
+            assert_eq!(out.len(), 2);
+            match decode("ab,,ba", &charset, ',').unwrap_err() {
+                N2NErr::EmptyInput => {}
+                _ => panic!("wrong error"),
+            }
+            match decode("ab,", &charset, ',').unwrap_err() {
+                N2NErr::EmptyInput => {}
+                _ => panic!("wrong error"),
+            }
+            let text = format!("a,{}", "c".repeat(50));
+            match decode(&text, &charset, ',').unwrap_err() {
+                N2NErr::TooLarge { max, .. } => assert_eq!(max, u64::MAX as u128),
+                _ => panic!("wrong error"),
+            }
+        }
+    }
+
+    #[test]
+    fn case_folded_non_ascii() -> Result<(), N2NErr> {
+        // The Kelvin sign lower cases to 'k', so it is in the charset even though it is not ASCII.
+        let charset = Charset::case_insensitive("abk");
+        assert_eq!(decode("a,\u{212A},b", &charset, ',')?, vec![0, 2, 1]);
+        match decode("a,\u{212A}é,b", &charset, ',').unwrap_err() {
+            N2NErr::InvalidCharacter {
+                character,
+                position,
+                ..
+            } => assert_eq!((character, position), ('é', 3)),
+            _ => panic!("wrong error"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn separator_in_charset() {
+        encode(&[1], &Charset::case_insensitive("abc"), 'B');
+    }
+}
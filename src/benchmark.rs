@@ -1,8 +1,20 @@
-use ::criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ::std::fmt::Write;
+
+use ::criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use ::number2name::Charset;
+use ::number2name::N2NInt;
 use ::number2name::BASE32HUMAN;
+use ::number2name::{Base32Human, StaticCharset};
+use ::number2name::{
+    BASE32, BASE32CROCKFORD, BASE32HEX, BASE32LOWERCASE, BASE32SCNY, BASE64, BASE64URL, HEX,
+    HEXLOWERCASE,
+};
 
-#[allow(clippy::zero_prefixed_literal)]
+#[allow(
+    clippy::zero_prefixed_literal,
+    clippy::explicit_auto_deref,
+    clippy::legacy_numeric_constants
+)]
 pub fn encode(c: &mut Criterion) {
     let charset: &Charset = &*BASE32HUMAN;
     c.bench_function("encoding", |b| {
@@ -111,6 +123,51 @@ pub fn encode(c: &mut Criterion) {
     });
 }
 
+pub fn encode_without_allocation(c: &mut Criterion) {
+    let charset: &Charset = &BASE32HUMAN;
+    let numbers: Vec<u64> = (0..100u64)
+        .map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> (i % 64))
+        .collect();
+    let mut group = c.benchmark_group("encoding without allocation");
+    group.bench_function("new string", |b| {
+        b.iter(|| {
+            for &number in &numbers {
+                black_box(charset.encode(black_box(number)));
+            }
+        })
+    });
+    group.bench_function("reused string", |b| {
+        let mut name = String::with_capacity(16);
+        b.iter(|| {
+            for &number in &numbers {
+                name.clear();
+                charset.encode_into(black_box(number), &mut name).unwrap();
+                black_box(&name);
+            }
+        })
+    });
+    group.bench_function("byte slice", |b| {
+        let mut buffer = [0u8; 16];
+        b.iter(|| {
+            for &number in &numbers {
+                let length = charset.encode_to_slice(black_box(number), &mut buffer);
+                black_box(&buffer[..length]);
+            }
+        })
+    });
+    group.bench_function("display", |b| {
+        let mut text = String::with_capacity(2048);
+        b.iter(|| {
+            text.clear();
+            for &number in &numbers {
+                write!(text, "{} ", charset.display(black_box(number))).unwrap();
+            }
+            black_box(&text);
+        })
+    });
+    group.finish();
+}
+
 #[allow(clippy::zero_prefixed_literal)]
 pub fn decode_case_sensitive(c: &mut Criterion) {
     let charset = Charset::case_sensitive("abcdefghjkmnpqrstuvwxyz23456789_");
@@ -191,28 +248,28 @@ pub fn decode_case_sensitive(c: &mut Criterion) {
             charset.decode(black_box("2drhaxr2x8hh")).unwrap();
             charset.decode(black_box("r2hhdrx5am88")).unwrap();
             charset.decode(black_box("dh_ha58885ar")).unwrap();
-            charset.decode(black_box("5arm5x5a_2hxh")).unwrap();
-            charset.decode(black_box("2552x8marr8a5")).unwrap();
+            charset.decode(black_box("5arm5x5a_2hxh")).unwrap_err();
+            charset.decode(black_box("2552x8marr8a5")).unwrap_err();
             charset.decode(black_box("r8a5a2222rmmd")).unwrap();
             charset.decode(black_box("m_amahh2xrm2d")).unwrap();
             charset.decode(black_box("rr8drxd8a5xa5")).unwrap();
             charset.decode(black_box("h_d8a55_5mxd5")).unwrap();
-            charset.decode(black_box("_hxar5252r_x5")).unwrap();
+            charset.decode(black_box("_hxar5252r_x5")).unwrap_err();
             charset.decode(black_box("addrx_hx25mmx")).unwrap();
-            charset.decode(black_box("_xdxmxm__a2hh")).unwrap();
+            charset.decode(black_box("_xdxmxm__a2hh")).unwrap_err();
             charset.decode(black_box("hddr2axxhrra2")).unwrap();
-            charset.decode(black_box("8hdh8aha22mra")).unwrap();
-            charset.decode(black_box("x5_x5a___a_hh")).unwrap();
+            charset.decode(black_box("8hdh8aha22mra")).unwrap_err();
+            charset.decode(black_box("x5_x5a___a_hh")).unwrap_err();
             charset.decode(black_box("h5_haxaa2285x")).unwrap();
             charset.decode(black_box("h58mh5h2rhdrm")).unwrap();
-            charset.decode(black_box("5rarrha2rh52m")).unwrap();
-            charset.decode(black_box("82_8xa5a8_2xr")).unwrap();
-            charset.decode(black_box("_rda222xr8x_d")).unwrap();
-            charset.decode(black_box("8a528xr2_rdrx")).unwrap();
-            charset.decode(black_box("8xrm52r2ar82h")).unwrap();
+            charset.decode(black_box("5rarrha2rh52m")).unwrap_err();
+            charset.decode(black_box("82_8xa5a8_2xr")).unwrap_err();
+            charset.decode(black_box("_rda222xr8x_d")).unwrap_err();
+            charset.decode(black_box("8a528xr2_rdrx")).unwrap_err();
+            charset.decode(black_box("8xrm52r2ar82h")).unwrap_err();
             charset.decode(black_box("h5m8rm558_m28")).unwrap();
             charset.decode(black_box("mhr88_2h8mr2d")).unwrap();
-            charset.decode(black_box("x_2x58525ah5h")).unwrap();
+            charset.decode(black_box("x_2x58525ah5h")).unwrap_err();
             charset.decode(black_box("rh2a5dm_x_88r2")).unwrap_err();
             charset.decode(black_box("rh82m2_a_h8822")).unwrap_err();
             charset.decode(black_box("85adma888h88_2")).unwrap_err();
@@ -220,7 +277,7 @@ pub fn decode_case_sensitive(c: &mut Criterion) {
     });
 }
 
-#[allow(clippy::zero_prefixed_literal)]
+#[allow(clippy::zero_prefixed_literal, clippy::explicit_auto_deref)]
 pub fn decode_case_insensitive(c: &mut Criterion) {
     let charset: &Charset = &*BASE32HUMAN;
     c.bench_function("decoding CI", |b| {
@@ -300,28 +357,28 @@ pub fn decode_case_insensitive(c: &mut Criterion) {
             charset.decode(black_box("2DRHAXR2X8HH")).unwrap();
             charset.decode(black_box("R2HHDRX5AM88")).unwrap();
             charset.decode(black_box("DH_HA58885AR")).unwrap();
-            charset.decode(black_box("5ARM5X5A_2HXH")).unwrap();
-            charset.decode(black_box("2552X8MARR8A5")).unwrap();
+            charset.decode(black_box("5ARM5X5A_2HXH")).unwrap_err();
+            charset.decode(black_box("2552X8MARR8A5")).unwrap_err();
             charset.decode(black_box("R8A5A2222RMMD")).unwrap();
             charset.decode(black_box("M_AMAHH2XRM2D")).unwrap();
             charset.decode(black_box("RR8DRXD8A5XA5")).unwrap();
             charset.decode(black_box("H_D8A55_5MXD5")).unwrap();
-            charset.decode(black_box("_HXAR5252R_X5")).unwrap();
+            charset.decode(black_box("_HXAR5252R_X5")).unwrap_err();
             charset.decode(black_box("ADDRX_HX25MMX")).unwrap();
-            charset.decode(black_box("_XDXMXM__A2HH")).unwrap();
+            charset.decode(black_box("_XDXMXM__A2HH")).unwrap_err();
             charset.decode(black_box("HDDR2AXXHRRA2")).unwrap();
-            charset.decode(black_box("8HDH8AHA22MRA")).unwrap();
-            charset.decode(black_box("X5_X5A___A_HH")).unwrap();
+            charset.decode(black_box("8HDH8AHA22MRA")).unwrap_err();
+            charset.decode(black_box("X5_X5A___A_HH")).unwrap_err();
             charset.decode(black_box("H5_HAXAA2285X")).unwrap();
             charset.decode(black_box("H58MH5H2RHDRM")).unwrap();
-            charset.decode(black_box("5RARRHA2RH52M")).unwrap();
-            charset.decode(black_box("82_8XA5A8_2XR")).unwrap();
-            charset.decode(black_box("_RDA222XR8X_D")).unwrap();
-            charset.decode(black_box("8A528XR2_RDRX")).unwrap();
-            charset.decode(black_box("8XRM52R2AR82H")).unwrap();
+            charset.decode(black_box("5RARRHA2RH52M")).unwrap_err();
+            charset.decode(black_box("82_8XA5A8_2XR")).unwrap_err();
+            charset.decode(black_box("_RDA222XR8X_D")).unwrap_err();
+            charset.decode(black_box("8A528XR2_RDRX")).unwrap_err();
+            charset.decode(black_box("8XRM52R2AR82H")).unwrap_err();
             charset.decode(black_box("H5M8RM558_M28")).unwrap();
             charset.decode(black_box("MHR88_2H8MR2D")).unwrap();
-            charset.decode(black_box("X_2X58525AH5H")).unwrap();
+            charset.decode(black_box("X_2X58525AH5H")).unwrap_err();
             charset.decode(black_box("RH2A5DM_X_88R2")).unwrap_err();
             charset.decode(black_box("RH82M2_A_H8822")).unwrap_err();
             charset.decode(black_box("85ADMA888H88_2")).unwrap_err();
@@ -329,10 +386,207 @@ pub fn decode_case_insensitive(c: &mut Criterion) {
     });
 }
 
+pub fn decode_failure(c: &mut Criterion) {
+    let charset: &Charset = &BASE32HUMAN;
+    let mut group = c.benchmark_group("decoding failure");
+    group.bench_function("invalid character", |b| {
+        b.iter(|| {
+            for name in ["x5!", "dh-a", "0d8r", "2ar ", "!_82"] {
+                charset.decode_u64(black_box(name)).unwrap_err();
+            }
+        })
+    });
+    group.bench_function("many invalid characters", |b| {
+        b.iter(|| {
+            for name in ["x5!-", "d-h-a", "0d0r0", "-2ar- ", "!_8!2"] {
+                charset.decode_u64(black_box(name)).unwrap_err();
+            }
+        })
+    });
+    group.bench_function("too large", |b| {
+        b.iter(|| {
+            for name in ["X_2X58525AH5H", "RH2A5DM_X_88R2", "RH82M2_A_H8822"] {
+                charset.decode_u64(black_box(name)).unwrap_err();
+            }
+        })
+    });
+    group.bench_function("too large for type", |b| {
+        b.iter(|| {
+            for name in ["x52", "dha", "d8r", "2ar", "_82"] {
+                charset.decode_int::<u8>(black_box(name)).unwrap_err();
+            }
+        })
+    });
+    group.finish();
+}
+
+pub fn static_charset(c: &mut Criterion) {
+    let charset: &Charset = &BASE32HUMAN;
+    let numbers = [576u64, 651_749, 917_997_698_978, 704_375_709_625_294_469];
+    let names = ["dx888a", "h5dh2x", "_d5__52_8_58", "r8a5a2222rmmd"];
+    let mut group = c.benchmark_group("static charset");
+    let mut name = String::with_capacity(16);
+    group.bench_function("encode Charset", |b| {
+        b.iter(|| {
+            for number in numbers {
+                name.clear();
+                charset.encode_into(black_box(number), &mut name).unwrap();
+            }
+        })
+    });
+    group.bench_function("encode StaticCharset", |b| {
+        b.iter(|| {
+            for number in numbers {
+                name.clear();
+                Base32Human::encode_into(black_box(number), &mut name).unwrap();
+            }
+        })
+    });
+    group.bench_function("decode Charset", |b| {
+        b.iter(|| {
+            for name in names {
+                charset.decode_int::<u64>(black_box(name)).unwrap();
+            }
+        })
+    });
+    group.bench_function("decode StaticCharset", |b| {
+        b.iter(|| {
+            for name in names {
+                Base32Human::decode::<u64>(black_box(name)).unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+pub fn batch(c: &mut Criterion) {
+    let charset: &Charset = &BASE32HUMAN;
+    let numbers: Vec<u64> = (0..10_000u64)
+        .map(|index| index.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> (index % 64))
+        .collect();
+    let mut text = Vec::new();
+    charset.encode_batch(&numbers, &mut text, ',');
+    let text = String::from_utf8(text).unwrap();
+    let mut group = c.benchmark_group("batch of 10k");
+    group.bench_function("encode per call", |b| {
+        let mut out = Vec::new();
+        b.iter(|| {
+            out.clear();
+            for (index, &number) in black_box(&numbers).iter().enumerate() {
+                if index > 0 {
+                    out.push(b',');
+                }
+                out.extend_from_slice(charset.encode_u64(number).as_bytes());
+            }
+        })
+    });
+    group.bench_function("encode_batch", |b| {
+        let mut out = Vec::new();
+        b.iter(|| {
+            out.clear();
+            charset.encode_batch(black_box(&numbers), &mut out, ',');
+        })
+    });
+    group.bench_function("decode per call", |b| {
+        let mut out = Vec::new();
+        b.iter(|| {
+            out.clear();
+            for name in black_box(&text).split(',') {
+                out.push(charset.decode_u64(name).unwrap());
+            }
+        })
+    });
+    group.bench_function("decode_batch", |b| {
+        let mut out = Vec::new();
+        b.iter(|| {
+            out.clear();
+            charset
+                .decode_batch(black_box(&text), ',', &mut out)
+                .unwrap();
+        })
+    });
+    group.finish();
+}
+
+fn encode_numbers<T: N2NInt>(
+    group: &mut ::criterion::BenchmarkGroup<'_, ::criterion::measurement::WallTime>,
+    name: &str,
+    charset: &Charset,
+    width: &str,
+    numbers: &[T],
+) {
+    let mut text = String::with_capacity(128);
+    group.bench_with_input(BenchmarkId::new(name, width), numbers, |b, numbers| {
+        b.iter(|| {
+            for &number in numbers {
+                text.clear();
+                charset.encode_into(black_box(number), &mut text).unwrap();
+            }
+        })
+    });
+}
+
+/// The division by the charset size depends on the size (power of two or not) and on the width.
+pub fn encode_by_width(c: &mut Criterion) {
+    let charsets: [(&str, &Charset); 10] = [
+        ("HEX", &HEX),
+        ("HEXLOWERCASE", &HEXLOWERCASE),
+        ("BASE32", &BASE32),
+        ("BASE32LOWERCASE", &BASE32LOWERCASE),
+        ("BASE32HUMAN", &BASE32HUMAN),
+        ("BASE32CROCKFORD", &BASE32CROCKFORD),
+        ("BASE32SCNY", &BASE32SCNY),
+        ("BASE32HEX", &BASE32HEX),
+        ("BASE64", &BASE64),
+        ("BASE64URL", &BASE64URL),
+    ];
+    let mut group = c.benchmark_group("encoding by width");
+    for (name, charset) in charsets {
+        encode_numbers(
+            &mut group,
+            name,
+            charset,
+            "u16",
+            &[576u16, 42_010, u16::MAX],
+        );
+        encode_numbers(
+            &mut group,
+            name,
+            charset,
+            "u32",
+            &[651_749u32, 3_519_164_357, u32::MAX],
+        );
+        encode_numbers(
+            &mut group,
+            name,
+            charset,
+            "u64",
+            &[917_997_698_978u64, 704_375_709_625_294_469, u64::MAX],
+        );
+        encode_numbers(
+            &mut group,
+            name,
+            charset,
+            "u128",
+            &[
+                586_675_931_082_352_445_193_904u128,
+                277_653_487_913_120_983_847_245_736_152_476_893_111,
+                u128::MAX,
+            ],
+        );
+    }
+    group.finish();
+}
+
 criterion_group!(
     benches,
     encode,
+    encode_without_allocation,
     decode_case_sensitive,
     decode_case_insensitive,
+    decode_failure,
+    static_charset,
+    batch,
+    encode_by_width,
 );
 criterion_main!(benches);
@@ -1,24 +1,137 @@
 use ::lazy_static::lazy_static;
 
-use crate::charset::Charset;
-
-lazy_static! {
-    pub static ref HEX: Charset = Charset::case_insensitive("0123456789abcdef");
-    pub static ref HEXLOWERCASE: Charset = Charset::case_sensitive("0123456789abcdef");
-    pub static ref BASE32: Charset = Charset::case_insensitive("ABCDEFGHIJKLMNOPQRSTUVWXYZ234567");
-    pub static ref BASE32LOWERCASE: Charset = Charset::case_sensitive("ABCDEFGHIJKLMNOPQRSTUVWXYZ234567");
-    pub static ref BASE32HUMAN: Charset = Charset::case_insensitive("abcdefghjkmnpqrstuvwxyz23456789_");
-    // Note: unlike 'real' Crockford, this does not accept e.g. L as 1 when decoding.
-    pub static ref BASE32CROCKFORD: Charset = Charset::case_insensitive("0123456789ABCDEFGHJKMNPQRSTVWXYZ");
-    pub static ref BASE32SCNY: Charset = Charset::case_sensitive("一二三四五六七八九十鼠牛虎兔龍蛇马羊猴鸡狗猪凤北东南西中左右上下");
-    pub static ref BASE32HEX: Charset = Charset::case_insensitive("0123456789ABCDEFGHIJKLMNOPQRSTUV");
-    pub static ref BASE64: Charset = Charset::case_sensitive("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/");
-    pub static ref BASE64URL: Charset = Charset::case_sensitive("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_");
+use crate::charset::{Case, Charset};
+use crate::static_charset::StaticCharset;
+use crate::util::chars_of;
+
+/// Defines each built-in charset both as a `Charset` and as a `StaticCharset` type.
+macro_rules! builtin_charsets {
+    ($($(#[$meta: meta])* $name: ident, $marker: ident, $case: ident, $size: literal, $data: literal;)*) => {
+        lazy_static! {
+            $($(#[$meta])* pub static ref $name: Charset = Charset::new($data, Case::$case);)*
+        }
+
+        $(
+            #[doc = concat!("The built-in charset `", stringify!($name), "` as a type, for `Name`.")]
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+            pub struct $marker;
+
+            impl StaticCharset for $marker {
+                const CHARS: &'static [char] = &chars_of::<$size>($data);
+                const CASE_INSENSITIVE: bool = matches!(Case::$case, Case::Insensitive);
+
+                fn charset() -> &'static Charset {
+                    &$name
+                }
+            }
+        )*
+
+        /// The built-in charset with this name, like `"BASE32HUMAN"` (ignoring case).
+        pub fn builtin_charset(name: &str) -> Option<&'static Charset> {
+            $(
+                if name.eq_ignore_ascii_case(stringify!($name)) {
+                    return Some(&$name);
+                }
+            )*
+            None
+        }
+
+        /// All built-in charsets with their names, like `("BASE32HUMAN", &BASE32HUMAN)`.
+        pub fn builtin_charsets() -> impl Iterator<Item = (&'static str, &'static Charset)> {
+            [$((stringify!($name), &*$name)),*].into_iter()
+        }
+    };
 }
 
+builtin_charsets! {
+    HEX, Hex, Insensitive, 16, "0123456789abcdef";
+    HEXLOWERCASE, HexLowercase, Sensitive, 16, "0123456789abcdef";
+    BASE32, Base32, Insensitive, 32, "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+    BASE32LOWERCASE, Base32Lowercase, Sensitive, 32, "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+    BASE32HUMAN, Base32Human, Insensitive, 32, "abcdefghjkmnpqrstuvwxyz23456789_";
+    /// Note: unlike 'real' Crockford, this does not accept e.g. L as 1 when decoding.
+    BASE32CROCKFORD, Base32Crockford, Insensitive, 32, "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+    BASE32SCNY, Base32Scny, Sensitive, 32, "一二三四五六七八九十鼠牛虎兔龍蛇马羊猴鸡狗猪凤北东南西中左右上下";
+    BASE32HEX, Base32Hex, Insensitive, 32, "0123456789ABCDEFGHIJKLMNOPQRSTUV";
+    BASE64, Base64, Sensitive, 64, "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    BASE64URL, Base64Url, Sensitive, 64, "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+}
 #[cfg(test)]
 mod tests {
+    use ::core::fmt::Debug;
+    use ::core::ptr;
+
     use super::*;
+    use crate::int::N2NInt;
+
+    fn same_as_charset<S: StaticCharset, T: N2NInt + PartialEq + Debug>(numbers: &[T]) {
+        let charset = S::charset();
+        assert_eq!(S::CHARS.len(), charset.len());
+        for (index, &character) in S::CHARS.iter().enumerate() {
+            assert_eq!(character, charset[index]);
+        }
+        for &number in numbers {
+            let mut name = String::new();
+            S::encode_into(number, &mut name).unwrap();
+            assert_eq!(name, charset.encode_int(number));
+            assert_eq!(S::decode::<T>(&name).unwrap(), number);
+            assert_eq!(
+                S::decode::<T>(&name.to_uppercase()).is_ok(),
+                charset.decode_int::<T>(&name.to_uppercase()).is_ok()
+            );
+            assert_eq!(
+                S::decode::<T>(&name.to_lowercase()).is_ok(),
+                charset.decode_int::<T>(&name.to_lowercase()).is_ok()
+            );
+        }
+    }
+
+    #[test]
+    fn static_charsets() {
+        let numbers = [
+            0u128,
+            1,
+            31,
+            32,
+            1000,
+            u64::MAX as u128,
+            u64::MAX as u128 + 1,
+            u128::MAX,
+        ];
+        same_as_charset::<Hex, _>(&numbers);
+        same_as_charset::<HexLowercase, _>(&numbers);
+        same_as_charset::<Base32, _>(&numbers);
+        same_as_charset::<Base32Lowercase, _>(&numbers);
+        same_as_charset::<Base32Human, _>(&numbers);
+        same_as_charset::<Base32Crockford, _>(&numbers);
+        same_as_charset::<Base32Scny, _>(&numbers);
+        same_as_charset::<Base32Hex, _>(&numbers);
+        same_as_charset::<Base64, _>(&numbers);
+        same_as_charset::<Base64Url, _>(&numbers);
+        same_as_charset::<Base32Human, _>(&[i8::MIN, -1, 0, i8::MAX]);
+    }
+
+    #[test]
+    fn by_name() {
+        assert!(ptr::eq(
+            builtin_charset("BASE32HUMAN").unwrap(),
+            &*BASE32HUMAN
+        ));
+        assert!(ptr::eq(builtin_charset("base64url").unwrap(), &*BASE64URL));
+        assert!(builtin_charset("base").is_none());
+        assert!(builtin_charset("'abc'").is_none());
+    }
+
+    #[test]
+    fn all_by_name() {
+        let all: Vec<(&str, &Charset)> = builtin_charsets().collect();
+        assert_eq!(all.len(), 10);
+        assert_eq!(all[0].0, "HEX");
+        for (name, charset) in all {
+            assert!(ptr::eq(builtin_charset(name).unwrap(), charset));
+        }
+    }
 
     #[test]
     fn hex() {
@@ -0,0 +1,197 @@
+use crate::Charset;
+
+/// Integer types that can be encoded, with the largest number that is encoded for them
+/// (for signed types, the unsigned number that negative numbers are mapped to).
+pub trait IntWidth {
+    const ENCODED_MAX: u128;
+}
+
+macro_rules! int_width_for_type {
+    ($int: ty, $unsigned: ty) => {
+        impl IntWidth for $int {
+            const ENCODED_MAX: u128 = <$unsigned>::MAX as u128;
+        }
+    };
+}
+
+int_width_for_type!(u8, u8);
+int_width_for_type!(u16, u16);
+int_width_for_type!(u32, u32);
+int_width_for_type!(u64, u64);
+int_width_for_type!(u128, u128);
+int_width_for_type!(usize, usize);
+int_width_for_type!(i8, u8);
+int_width_for_type!(i16, u16);
+int_width_for_type!(i32, u32);
+int_width_for_type!(i64, u64);
+int_width_for_type!(i128, u128);
+int_width_for_type!(isize, usize);
+
+/// Number of characters in the name of the number.
+/// Panics if it does not fit in a usize (only possible for a single-character charset).
+pub(crate) fn encoded_len(number: u128, charset: &Charset) -> usize {
+    let size = charset.len() as u128;
+    if size == 1 {
+        return usize::try_from(number)
+            .ok()
+            .and_then(|number| number.checked_add(1))
+            .expect("name length does not fit in a usize");
+    }
+    let mut remainder = number;
+    let mut length = 1;
+    while remainder >= size {
+        remainder = remainder / size - 1;
+        length += 1;
+    }
+    length
+}
+
+/// Number of different names with exactly this many characters, if it fits in a u128.
+/// Zero for length zero, since no number has an empty name.
+pub(crate) fn count_of_length(length: usize, charset: &Charset) -> Option<u128> {
+    if length == 0 {
+        return Some(0);
+    }
+    (charset.len() as u128).checked_pow(u32::try_from(length).ok()?)
+}
+
+/// The smallest number whose name has this many characters, if it fits in a u128.
+pub(crate) fn first_of_length(length: usize, charset: &Charset) -> Option<u128> {
+    if length == 0 {
+        return None;
+    }
+    let size = charset.len() as u128;
+    let mut first: u128 = 0;
+    let mut count: u128 = 1;
+    for _ in 1..length {
+        count = count.checked_mul(size)?;
+        first = first.checked_add(count)?;
+    }
+    Some(first)
+}
+
+/// The largest number whose name has this many characters, if it fits in a u128.
+pub(crate) fn last_of_length(length: usize, charset: &Charset) -> Option<u128> {
+    let first = first_of_length(length, charset)?;
+    first.checked_add(count_of_length(length, charset)? - 1)
+}
+
+/// The smallest number of characters in a charset, such that at least `count` numbers
+/// have names of at most `max_len` characters. Empty if `max_len` is zero,
+/// or if the size does not fit in a usize.
+pub(crate) fn min_charset_size_for(count: u128, max_len: usize) -> Option<usize> {
+    if max_len == 0 {
+        return None;
+    }
+    let fits = |size: u128| {
+        if size == 1 {
+            return max_len as u128 >= count;
+        }
+        let mut total: u128 = 0;
+        let mut power: u128 = 1;
+        for _ in 0..max_len {
+            power = match power.checked_mul(size) {
+                Some(power) => power,
+                None => return true,
+            };
+            total = match total.checked_add(power) {
+                Some(total) => total,
+                None => return true,
+            };
+            if total >= count {
+                return true;
+            }
+        }
+        false
+    };
+    // With `count` characters, every number fits in a single character.
+    let mut low: u128 = 1;
+    let mut high: u128 = count.max(1);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if fits(middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+    usize::try_from(low).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lengths() {
+        let charset = Charset::case_sensitive("abc");
+        for number in 0..500 {
+            assert_eq!(
+                encoded_len(number, &charset),
+                charset.encode_u128(number).len()
+            );
+        }
+        assert_eq!(
+            encoded_len(u128::MAX, &charset),
+            charset.encode_u128(u128::MAX).len()
+        );
+        let unary = Charset::case_sensitive("a");
+        assert_eq!(encoded_len(6, &unary), 7);
+        assert_eq!(encoded_len(0, &unary), 1);
+        assert_eq!(encoded_len(usize::MAX as u128 - 1, &unary), usize::MAX);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unary_length_overflow() {
+        encoded_len(u128::MAX, &Charset::case_sensitive("a"));
+    }
+
+    #[test]
+    fn length_bounds() {
+        let charset = Charset::case_sensitive("abc");
+        assert_eq!(count_of_length(0, &charset), Some(0));
+        assert_eq!(count_of_length(3, &charset), Some(27));
+        assert_eq!(first_of_length(0, &charset), None);
+        assert_eq!(first_of_length(1, &charset), Some(0));
+        assert_eq!(first_of_length(3, &charset), Some(12));
+        assert_eq!(last_of_length(3, &charset), Some(38));
+        assert_eq!(charset.encode_u128(12), "aaa");
+        assert_eq!(charset.encode_u128(38), "ccc");
+    }
+
+    #[test]
+    fn length_bounds_overflow() {
+        let charset = Charset::case_sensitive("01");
+        let max_len = encoded_len(u128::MAX, &charset);
+        assert_eq!(max_len, 128);
+        assert!(first_of_length(max_len, &charset).is_some());
+        assert_eq!(last_of_length(max_len, &charset), None);
+        assert_eq!(first_of_length(max_len + 1, &charset), None);
+        assert_eq!(count_of_length(128, &charset), None);
+        assert_eq!(count_of_length(127, &charset), Some(1 << 127));
+    }
+
+    #[test]
+    fn max_len() {
+        let charset = Charset::case_sensitive("aBcDeFgHiJkLmNoPqRsTuVwXyZ");
+        assert_eq!(charset.max_len_for::<u16>(), 4);
+        assert_eq!(charset.max_len_for::<i16>(), 4);
+        assert_eq!(charset.max_len_for::<u64>(), 14);
+        assert_eq!(charset.max_len_for::<u128>(), 28);
+        assert_eq!(charset.max_len_for::<u8>(), 2);
+    }
+
+    #[test]
+    fn min_size() {
+        assert_eq!(min_charset_size_for(0, 3), Some(1));
+        assert_eq!(min_charset_size_for(1, 1), Some(1));
+        assert_eq!(min_charset_size_for(1000, 1), Some(1000));
+        // 3 + 9 + 27 = 39
+        assert_eq!(min_charset_size_for(39, 3), Some(3));
+        assert_eq!(min_charset_size_for(40, 3), Some(4));
+        assert_eq!(min_charset_size_for(u64::MAX as u128 + 1, 13), Some(31));
+        assert_eq!(min_charset_size_for(u128::MAX, 200), Some(2));
+        assert_eq!(min_charset_size_for(5, 0), None);
+    }
+}
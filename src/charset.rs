@@ -1,16 +1,37 @@
-use ::std::collections::HashMap;
-use ::std::fmt;
-use ::std::fmt::{Formatter, Write};
-use ::std::ops::Index;
-
-use crate::decode::name2number;
-use crate::encode::{number2name_u128, number2name_u16, number2name_u32, number2name_u64};
+use ::alloc::string::String;
+use ::alloc::sync::Arc;
+use ::alloc::vec::Vec;
+use ::core::cmp::Ordering;
+use ::core::fmt;
+use ::core::fmt::{Formatter, Write};
+use ::core::ops::{Index, RangeBounds};
+#[cfg(feature = "random")]
+use ::std::io;
+
+use crate::arith::{name_add, name_cmp, name_distance, name_pred, name_sub, name_succ};
+use crate::batch::{decode_batch, encode_batch, AsciiTable};
+use crate::capacity::{
+    count_of_length, encoded_len, first_of_length, last_of_length, min_charset_size_for, IntWidth,
+};
+use crate::decoder::Decoder;
+use crate::divide::Divisor;
+use crate::encode::{
+    number2name_to_slice, number2name_u128, number2name_u16, number2name_u32, number2name_u64,
+    NameDisplay,
+};
+use crate::group::Grouping;
+use crate::int::N2NInt;
+use crate::names::Names;
+#[cfg(feature = "rayon")]
+use crate::parallel::{par_decode, par_encode};
+#[cfg(feature = "random")]
+use crate::random::{random_number, random_token};
 use crate::typ::N2NErr;
-use crate::util::lower;
+use crate::util::try_lower;
 use crate::{
-    name2number_i128, name2number_i16, name2number_i32, name2number_i64, name2number_u128,
-    name2number_u16, name2number_u32, name2number_u64, number2name_i128, number2name_i16,
-    number2name_i32, number2name_i64,
+    name2number, name2number_i128, name2number_i16, name2number_i32, name2number_i64,
+    name2number_prefix, name2number_u128, name2number_u16, name2number_u32, name2number_u64,
+    number2name_i128, number2name_i16, number2name_i32, number2name_i64,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,11 +40,16 @@ pub enum Case {
     Insensitive,
 }
 
+/// Cloning is cheap, since the characters are shared.
 #[derive(Clone)]
 pub struct Charset {
-    values: Vec<char>,
-    lookup: HashMap<char, u64>,
+    values: Arc<[char]>,
+    /// Characters (lower-case if case-insensitive) with their index, sorted by character.
+    lookup: Arc<[(char, u64)]>,
     case: Case,
+    /// Byte lookups, if all characters are ASCII.
+    ascii: Option<Arc<AsciiTable>>,
+    divisor: Arc<Divisor>,
 }
 
 impl fmt::Debug for Charset {
@@ -34,7 +60,7 @@ impl fmt::Debug for Charset {
             Case::Insensitive => "case-insensitive",
         })?;
         f.write_str(" character set: [")?;
-        for character in &self.values {
+        for character in self.values.iter() {
             f.write_char(*character)?;
         }
         f.write_str("])")
@@ -43,7 +69,7 @@ impl fmt::Debug for Charset {
 
 impl fmt::Display for Charset {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        for character in &self.values {
+        for character in self.values.iter() {
             f.write_char(*character)?;
         }
         Ok(())
@@ -62,38 +88,57 @@ impl Charset {
         Charset::new(data, Case::Insensitive)
     }
 
-    /// Panics if the input contains duplicates.
+    /// Panics if the input contains duplicates, or if it is case-insensitive and has a character
+    /// whose lower-case is not a single character.
     pub fn new(data: impl AsRef<str>, case: Case) -> Self {
         match Charset::try_new(data, case) {
             Some(charset) => charset,
-            None => panic!("failed to initialize charset due to duplicate data"),
+            None => panic!(
+                "failed to initialize charset due to duplicate data, or characters without a \
+                single-character lower-case"
+            ),
         }
     }
 
-    /// Empty if the input contains duplicates or is empty.
+    /// Empty if the input contains duplicates or is empty, or if it is case-insensitive and has a
+    /// character whose lower-case is not a single character.
     pub fn try_new(data: impl AsRef<str>, case: Case) -> Option<Self> {
         let data = data.as_ref();
         if data.is_empty() {
             return None;
         }
-        let mut lookup = HashMap::new();
+        let mut lookup = Vec::with_capacity(data.len());
         let mut values = Vec::with_capacity(data.len());
         for (index, character) in data.chars().enumerate() {
             let unique_repr = match case {
                 Case::Sensitive => character,
-                Case::Insensitive => lower(character),
+                Case::Insensitive => try_lower(character)?,
             };
-            if lookup.contains_key(&unique_repr) {
-                return None;
-            }
-            lookup.insert(unique_repr, index as u64);
+            lookup.push((unique_repr, index as u64));
             values.push(character)
         }
-        Some(Charset {
-            values,
-            lookup,
+        lookup.sort_unstable();
+        if lookup.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+            return None;
+        }
+        let mut charset = Charset {
+            divisor: Arc::new(Divisor::new(values.len() as u64)),
+            values: values.into(),
+            lookup: lookup.into(),
             case,
-        })
+            ascii: None,
+        };
+        charset.ascii = AsciiTable::for_charset(&charset).map(Arc::new);
+        Some(charset)
+    }
+
+    pub(crate) fn ascii(&self) -> Option<&AsciiTable> {
+        self.ascii.as_deref()
+    }
+
+    /// How to divide by the number of characters.
+    pub(crate) fn divisor(&self) -> &Divisor {
+        &self.divisor
     }
 
     /// Number of characters.
@@ -101,86 +146,285 @@ impl Charset {
         self.values.len()
     }
 
+    /// Number of characters in the name of the number.
+    /// Panics if it does not fit in a usize, which is only possible for a single-character charset.
+    pub fn encoded_len(&self, number: u128) -> usize {
+        encoded_len(number, self)
+    }
+
+    /// Number of different names with exactly this many characters, if it fits in a u128.
+    pub fn count_of_length(&self, length: usize) -> Option<u128> {
+        count_of_length(length, self)
+    }
+
+    /// The smallest number whose name has this many characters, if it fits in a u128.
+    pub fn first_of_length(&self, length: usize) -> Option<u128> {
+        first_of_length(length, self)
+    }
+
+    /// The largest number whose name has this many characters, if it fits in a u128.
+    pub fn last_of_length(&self, length: usize) -> Option<u128> {
+        last_of_length(length, self)
+    }
+
+    /// The longest name for any value of the integer type (e.g. `max_len_for::<i32>()`).
+    /// Panics like `encoded_len` if it does not fit in a usize.
+    pub fn max_len_for<T: IntWidth>(&self) -> usize {
+        encoded_len(T::ENCODED_MAX, self)
+    }
+
+    /// The smallest charset size that gives at least `count` names of at most `max_len` characters.
+    /// Empty if `max_len` is zero, or if the size does not fit in a usize.
+    pub fn min_charset_size_for(count: u128, max_len: usize) -> Option<usize> {
+        min_charset_size_for(count, max_len)
+    }
+
     /// Find the numberical position of a character.
     pub fn index_of(&self, character: char) -> Option<u64> {
         let representation = if self.case == Case::Sensitive {
             character
         } else {
-            lower(character)
+            // Charset characters have single-character lowercase, so others cannot match.
+            try_lower(character)?
         };
-        self.lookup.get(&representation).cloned()
+        self.lookup
+            .binary_search_by_key(&representation, |&(character, _)| character)
+            .ok()
+            .map(|position| self.lookup[position].1)
     }
 
     pub fn encode(&self, number: u64) -> String {
-        number2name_u64(number, &self)
+        number2name_u64(number, self)
+    }
+
+    /// The name of a number of any integer type, e.g. `encode_int(-3i32)`.
+    pub fn encode_int<T: N2NInt>(&self, number: T) -> String {
+        let mut name = String::new();
+        number
+            .encode_into(self, &mut name)
+            .expect("writing to a String does not fail");
+        name
+    }
+
+    /// Append the names of the numbers to `out` as UTF-8, with the separator between them.
+    /// Faster than encoding one by one, especially for ASCII charsets. Panics if the separator is in the charset.
+    pub fn encode_batch(&self, numbers: &[u64], out: &mut Vec<u8>, separator: char) {
+        encode_batch(numbers, self, out, separator)
+    }
+
+    /// Decode names separated by the separator, appending the numbers to `out`.
+    /// On failure, `out` has the numbers before the failing name.
+    pub fn decode_batch(
+        &self,
+        text: &str,
+        separator: char,
+        out: &mut Vec<u64>,
+    ) -> Result<(), N2NErr> {
+        decode_batch(text, self, separator, out)
+    }
+
+    /// The names of the numbers, in the same order, using all cores.
+    #[cfg(feature = "rayon")]
+    pub fn par_encode<T: N2NInt + Send + Sync>(&self, numbers: &[T]) -> Vec<String> {
+        par_encode(numbers, self)
+    }
+
+    /// Decode all names using all cores, keeping the order. Fails with the index and error of the first name that fails.
+    #[cfg(feature = "rayon")]
+    #[allow(clippy::result_large_err)]
+    pub fn par_decode<T: N2NInt + Send, S: AsRef<str> + Sync>(
+        &self,
+        names: &[S],
+    ) -> Result<Vec<T>, (usize, N2NErr)> {
+        par_decode(names, self)
     }
 
     pub fn encode_u16(&self, number: u16) -> String {
-        number2name_u16(number, &self)
+        number2name_u16(number, self)
     }
 
     pub fn encode_u32(&self, number: u32) -> String {
-        number2name_u32(number, &self)
+        number2name_u32(number, self)
     }
 
     pub fn encode_u64(&self, number: u64) -> String {
-        number2name_u64(number, &self)
+        number2name_u64(number, self)
     }
 
     pub fn encode_u128(&self, number: u128) -> String {
-        number2name_u128(number, &self)
+        number2name_u128(number, self)
     }
 
     pub fn encode_i16(&self, number: i16) -> String {
-        number2name_i16(number, &self)
+        number2name_i16(number, self)
     }
 
     pub fn encode_i32(&self, number: i32) -> String {
-        number2name_i32(number, &self)
+        number2name_i32(number, self)
     }
 
     pub fn encode_i64(&self, number: i64) -> String {
-        number2name_i64(number, &self)
+        number2name_i64(number, self)
     }
 
     pub fn encode_i128(&self, number: i128) -> String {
-        number2name_i128(number, &self)
+        number2name_i128(number, self)
+    }
+
+    /// Write the name of a number, without allocating.
+    pub fn encode_into<T: N2NInt>(&self, number: T, out: &mut impl fmt::Write) -> fmt::Result {
+        number.encode_into(self, out)
+    }
+
+    /// Write the name of a number into the buffer as UTF-8, without allocating,
+    /// and return the number of bytes written. Panics if the buffer is too small.
+    pub fn encode_to_slice<T: N2NInt>(&self, number: T, buffer: &mut [u8]) -> usize {
+        number2name_to_slice(number, self, buffer)
+    }
+
+    /// Something that formats as the name of the number, e.g. `format!("id-{}", charset.display(42u64))`.
+    pub fn display<T: N2NInt>(&self, number: T) -> NameDisplay<'_, T> {
+        NameDisplay {
+            number,
+            charset: self,
+        }
     }
 
     pub fn decode(&self, text: impl AsRef<str>) -> Result<u64, N2NErr> {
-        name2number(text, &self)
+        name2number(text, self)
+    }
+
+    /// Convert a name back to a number of the given type, e.g. `decode_int::<u32>("abc")`.
+    pub fn decode_int<T: N2NInt>(&self, text: impl AsRef<str>) -> Result<T, N2NErr> {
+        T::decode(text.as_ref(), self)
+    }
+
+    /// A decoder to which characters of a name can be added one at a time.
+    pub fn decoder<T: N2NInt>(&self) -> Decoder<'_, T> {
+        Decoder::new(self)
+    }
+
+    /// Decode the name at the start of the text, returning the number and the name length in bytes.
+    /// See `name2number_prefix`.
+    pub fn decode_prefix<T: N2NInt>(&self, text: &str) -> Result<(T, usize), N2NErr> {
+        name2number_prefix(text, self)
     }
 
     pub fn decode_u16(&self, text: impl AsRef<str>) -> Result<u16, N2NErr> {
-        name2number_u16(text, &self)
+        name2number_u16(text, self)
     }
 
     pub fn decode_u32(&self, text: impl AsRef<str>) -> Result<u32, N2NErr> {
-        name2number_u32(text, &self)
+        name2number_u32(text, self)
     }
 
     pub fn decode_u64(&self, text: impl AsRef<str>) -> Result<u64, N2NErr> {
-        name2number_u64(text, &self)
+        name2number_u64(text, self)
     }
 
     pub fn decode_u128(&self, text: impl AsRef<str>) -> Result<u128, N2NErr> {
-        name2number_u128(text, &self)
+        name2number_u128(text, self)
     }
 
     pub fn decode_i16(&self, text: impl AsRef<str>) -> Result<i16, N2NErr> {
-        name2number_i16(text, &self)
+        name2number_i16(text, self)
     }
 
     pub fn decode_i32(&self, text: impl AsRef<str>) -> Result<i32, N2NErr> {
-        name2number_i32(text, &self)
+        name2number_i32(text, self)
     }
 
     pub fn decode_i64(&self, text: impl AsRef<str>) -> Result<i64, N2NErr> {
-        name2number_i64(text, &self)
+        name2number_i64(text, self)
     }
 
     pub fn decode_i128(&self, text: impl AsRef<str>) -> Result<i128, N2NErr> {
-        name2number_i128(text, &self)
+        name2number_i128(text, self)
+    }
+
+    /// Encode the number and insert separators between groups of characters, like `ABCD-EFGH`.
+    /// Panics if a separator is part of this charset.
+    pub fn encode_grouped(&self, number: u64, grouping: &Grouping) -> String {
+        grouping.assert_separators(self);
+        grouping.format(self.encode_u64(number))
+    }
+
+    /// Decode a name after removing surrounding whitespace and separators.
+    /// Panics if a separator (including accepted ones) is part of this charset.
+    pub fn decode_grouped(
+        &self,
+        text: impl AsRef<str>,
+        grouping: &Grouping,
+    ) -> Result<u64, N2NErr> {
+        let text = text.as_ref();
+        // Report positions in the grouped text, rather than in the name without separators.
+        self.decode_u64(grouping.strip(text, self)?)
+            .map_err(|err| match err {
+                N2NErr::InvalidCharacter { .. } => grouping.invalid_in(text, self),
+                err => err,
+            })
+    }
+
+    /// The name of the next number, for names of any length.
+    pub fn succ(&self, name: impl AsRef<str>) -> Result<String, N2NErr> {
+        name_succ(name, self)
+    }
+
+    /// The name of the previous number (None for the first name), for names of any length.
+    pub fn pred(&self, name: impl AsRef<str>) -> Result<Option<String>, N2NErr> {
+        name_pred(name, self)
+    }
+
+    /// The name of the number that is `amount` higher, for names of any length.
+    /// Fails with `TooLarge` for a single-character charset if the name does not fit in memory.
+    pub fn add(&self, name: impl AsRef<str>, amount: u128) -> Result<String, N2NErr> {
+        name_add(name, amount, self)
+    }
+
+    /// The name of the number that is `amount` lower (None if below zero), for names of any length.
+    pub fn sub(&self, name: impl AsRef<str>, amount: u128) -> Result<Option<String>, N2NErr> {
+        name_sub(name, amount, self)
+    }
+
+    /// The difference between the numbers of two names, for names of any length.
+    pub fn distance(&self, left: impl AsRef<str>, right: impl AsRef<str>) -> Result<u128, N2NErr> {
+        name_distance(left, right, self)
+    }
+
+    /// Compare names in the order of the numbers they represent, for names of any length.
+    pub fn compare(
+        &self,
+        left: impl AsRef<str>,
+        right: impl AsRef<str>,
+    ) -> Result<Ordering, N2NErr> {
+        name_cmp(left, right, self)
+    }
+
+    /// A random name of the minimal length that has at least `bits` of entropy,
+    /// using the secure random source of the operating system.
+    /// Fails with `InvalidInput` for a single-character charset, which has no entropy, and for zero bits,
+    /// which would give an empty token.
+    #[cfg(feature = "random")]
+    pub fn random_token(&self, bits: u32) -> io::Result<String> {
+        random_token(bits, self)
+    }
+
+    /// The name of a uniformly random number in the range, using the secure random source of the operating system.
+    #[cfg(feature = "random")]
+    pub fn random_name(&self, range: impl RangeBounds<u128>) -> io::Result<String> {
+        Ok(self.encode_u128(random_number(range)?))
+    }
+
+    /// Iterate over the names of all numbers in the range, in order.
+    /// Empty for a single-character charset if the last name, which has a character for every number,
+    /// does not fit in memory (e.g. for an unbounded range).
+    pub fn names(&self, range: impl RangeBounds<u128>) -> Option<Names<'_>> {
+        Names::new(self, range)
+    }
+
+    /// Iterate over all names with the given number of characters, in order.
+    pub fn names_of_length(&self, length: usize) -> Names<'_> {
+        Names::of_length(self, length)
     }
 }
 
@@ -199,6 +443,14 @@ mod tests {
     mod create {
         use super::*;
 
+        #[test]
+        fn clone_shares_characters() {
+            let charset = Charset::case_insensitive("abc");
+            let copy = charset.clone();
+            assert!(Arc::ptr_eq(&charset.values, &copy.values));
+            assert!(Arc::ptr_eq(&charset.lookup, &copy.lookup));
+        }
+
         #[test]
         fn valid_charset() {
             let charset = Charset::try_new("Abc", Case::Insensitive);
@@ -227,6 +479,12 @@ mod tests {
             assert!(charset.is_none());
         }
 
+        #[test]
+        fn invalid_multi_character_lowercase() {
+            assert!(Charset::try_new("aİ", Case::Insensitive).is_none());
+            assert!(Charset::try_new("aİ", Case::Sensitive).is_some());
+        }
+
         #[test]
         #[should_panic]
         fn panic_mode() {
@@ -286,6 +544,13 @@ mod tests {
             Ok(())
         }
 
+        #[test]
+        fn multi_character_lowercase() {
+            let charset = Charset::case_insensitive("AbCd");
+            assert!(charset.index_of('İ').is_none());
+            assert!(charset.decode_int::<u64>("aİ").is_err());
+        }
+
         #[test]
         fn do_not_ignore_case() -> Result<(), ()> {
             let charset = Charset::case_sensitive("Ab");
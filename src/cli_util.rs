@@ -1,41 +1,129 @@
+use ::std::fs;
+use ::std::io::{stdin, stdout, BufWriter, Read, Write};
+use ::std::path::Path;
+use ::std::str::FromStr;
+
+#[cfg(feature = "rayon")]
+use ::rayon::prelude::*;
+
+use ::number2name::builtin_charset;
 use ::number2name::Charset;
-use ::number2name::BASE32;
-use ::number2name::BASE32CROCKFORD;
-use ::number2name::BASE32HEX;
-use ::number2name::BASE32HUMAN;
-use ::number2name::BASE32LOWERCASE;
-use ::number2name::BASE32SCNY;
-use ::number2name::BASE64;
-use ::number2name::BASE64URL;
-use ::number2name::HEX;
-use ::number2name::HEXLOWERCASE;
+use ::number2name::{GroupFrom, Grouping};
 
 pub fn charset_by_identifier(identifier: &str) -> Result<Charset, String> {
-    Ok(match identifier.to_uppercase().as_str() {
-        "HEX" => HEX.clone(),
-        "HEXLOWERCASE" => HEXLOWERCASE.clone(),
-        "BASE32" => BASE32.clone(),
-        "BASE32LOWERCASE" => BASE32LOWERCASE.clone(),
-        "BASE32HUMAN" => BASE32HUMAN.clone(),
-        "BASE32CROCKFORD" => BASE32CROCKFORD.clone(),
-        "BASE32SCNY" => BASE32SCNY.clone(),
-        "BASE32HEX" => BASE32HEX.clone(),
-        "BASE64" => BASE64.clone(),
-        "BASE64URL" => BASE64URL.clone(),
-        _ => {
-            let mut literal = identifier;
-            if literal.starts_with('\'') || literal.starts_with('"') {
-                literal = &literal[1..literal.len() - 1]
-            } else {
-                eprintln!(
-                    "Charset '{}' was not a built-in identifier and was not quoted; \
-                it will be treated as a literal set of characters, but it is recommended that \
-                you enclose it in quotes ('), so it is never interpreted as a name of a charset\
-                (you may need \"'..'\" because the shell strips the outer quotes)",
-                    &literal
-                )
-            }
-            Charset::case_sensitive(literal)
+    if let Some(charset) = builtin_charset(identifier) {
+        return Ok(charset.clone());
+    }
+    let mut literal = identifier;
+    if literal.starts_with('\'') || literal.starts_with('"') {
+        literal = &literal[1..literal.len() - 1]
+    } else {
+        eprintln!(
+            "Charset '{}' was not a built-in identifier and was not quoted; \
+        it will be treated as a literal set of characters, but it is recommended that \
+        you enclose it in quotes ('), so it is never interpreted as a name of a charset\
+        (you may need \"'..'\" because the shell strips the outer quotes)",
+            &literal
+        )
+    }
+    Ok(Charset::case_sensitive(literal))
+}
+
+/// The grouping requested by `--group`, `--separator` and `--from-right`, if any.
+pub fn grouping_by_args(
+    group: Option<usize>,
+    separator: char,
+    from_right: bool,
+    charset: &Charset,
+    scheme: Scheme,
+) -> Result<Option<Grouping>, String> {
+    let size = match group {
+        Some(0) => return Err("The group size should be at least 1".to_owned()),
+        Some(_) if scheme == Scheme::Proquint => {
+            return Err(
+                "Proquints are already grouped, so they cannot be used with --group".to_owned(),
+            )
+        }
+        Some(size) => size,
+        None => return Ok(None),
+    };
+    if charset.index_of(separator).is_some() {
+        return Err(format!(
+            "The separator '{}' cannot be used because it is part of the charset",
+            separator
+        ));
+    }
+    let from = if from_right {
+        GroupFrom::Right
+    } else {
+        GroupFrom::Left
+    };
+    Ok(Some(Grouping::new(size, separator).from_end(from)))
+}
+
+/// How numbers are turned into names: using the charset, as proquints or alternating consonants and vowels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    Charset,
+    Proquint,
+    Pronounceable,
+}
+
+pub const SCHEMES: &[&str] = &["charset", "proquint", "pronounceable"];
+
+impl FromStr for Scheme {
+    type Err = String;
+
+    fn from_str(identifier: &str) -> Result<Self, Self::Err> {
+        match identifier.to_lowercase().as_str() {
+            "charset" => Ok(Scheme::Charset),
+            "proquint" => Ok(Scheme::Proquint),
+            "pronounceable" => Ok(Scheme::Pronounceable),
+            _ => Err(format!(
+                "Unknown scheme '{}'; expected one of: {}",
+                identifier,
+                SCHEMES.join(", ")
+            )),
+        }
+    }
+}
+
+/// Convert each line of the file (or standard input for `-`), using all cores with the `rayon` feature,
+/// and print the results in order.
+/// Empty lines are skipped. Stops at the first line that fails, so the error does not depend on the threads.
+pub fn convert_lines(
+    path: &Path,
+    convert: impl Fn(&str) -> Result<String, String> + Sync,
+) -> Result<(), String> {
+    let read_err = |err: ::std::io::Error| format!("Could not read '{}': {}", path.display(), err);
+    let mut text = String::new();
+    if path == Path::new("-") {
+        stdin().read_to_string(&mut text).map_err(read_err)?;
+    } else {
+        text = fs::read_to_string(path).map_err(read_err)?;
+    }
+    let lines: Vec<&str> = text.lines().collect();
+    let convert_line = |line: &&str| {
+        Some(line.trim())
+            .filter(|line| !line.is_empty())
+            .map(&convert)
+    };
+    #[cfg(feature = "rayon")]
+    let results: Vec<Option<Result<String, String>>> = lines
+        .par_iter()
+        .with_min_len(1024)
+        .map(convert_line)
+        .collect();
+    #[cfg(not(feature = "rayon"))]
+    let results: Vec<Option<Result<String, String>>> = lines.iter().map(convert_line).collect();
+    let stdout = stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let write_err = |err: ::std::io::Error| format!("Could not write output: {}", err);
+    for (index, result) in results.into_iter().enumerate() {
+        if let Some(result) = result {
+            let converted = result.map_err(|err| format!("Line {}: {}", index + 1, err))?;
+            writeln!(out, "{}", converted).map_err(write_err)?;
         }
-    })
+    }
+    out.flush().map_err(write_err)
 }
@@ -1,62 +1,62 @@
+use crate::int::N2NInt;
 use crate::typ::N2NErr;
 use crate::{
     unsigned2signed_128, unsigned2signed_16, unsigned2signed_32, unsigned2signed_64, Charset,
 };
 
 macro_rules! name2number_for_type {
-    ($name: ident, $int:ty) => {
-        /// Convert a string encoded using the given charset back to the number it represents.
-        pub fn $name(text: impl AsRef<str>, charset: &Charset) -> Result<$int, N2NErr> {
-            fn get_index(character: char, charset: &Charset) -> Result<u64, N2NErr> {
-                match charset.index_of(character) {
-                    Some(i) => Ok(i as u64),
-                    None => Err(N2NErr::InvalidCharacter {
-                        character,
-                        charset: charset.clone(),
-                    }),
-                }
+    ($name: ident, $append: ident, $int:ty) => {
+        /// The number of the name with one more character (of index `digit`) at the end,
+        /// or None if it does not fit. Without a name so far, it is just the digit.
+        #[inline]
+        pub(crate) fn $append(number: Option<$int>, digit: u64, size: u64) -> Option<$int> {
+            match number {
+                // Like base-n, but each earlier character is worth one more, so there are no leading zeros.
+                Some(number) => number
+                    .checked_add(1)?
+                    .checked_mul(<$int>::try_from(size).ok()?)?
+                    .checked_add(digit as $int),
+                None => <$int>::try_from(digit).ok(),
             }
+        }
 
+        /// Convert a string encoded using the given charset back to the number it represents.
+        pub fn $name(text: impl AsRef<str>, charset: &Charset) -> Result<$int, N2NErr> {
             let text = text.as_ref();
-            let size = charset.len() as $int;
-            // Handle the first letter separately
-            let mut number: $int = if let Some(first_char) = text.chars().rev().next() {
-                get_index(first_char, charset)? as $int
-            } else {
-                return Err(N2NErr::EmptyInput);
-            };
-            // Handle the other letters, with special case for near-overflow
-            let mut scale: $int = 1;
-            for character in text.chars().rev().skip(1) {
-                let value = get_index(character, charset)? as $int;
-                match scale.checked_mul(size) {
-                    Some(new_scale) => {
-                        scale = new_scale;
-                        number = match number.checked_add((value + 1) * scale) {
-                            Some(n) => n,
-                            None => {
-                                return Err(N2NErr::TooLarge {
-                                    charset: charset.clone(),
-                                })
-                            }
-                        }
-                    }
+            let size = charset.len() as u64;
+            let mut number = None;
+            for character in text.chars() {
+                let digit = match charset.index_of(character) {
+                    Some(digit) => digit,
                     None => {
-                        return Err(N2NErr::TooLarge {
-                            charset: charset.clone(),
-                        })
+                        return Err(N2NErr::invalid_in(text, charset, |_, c| {
+                            charset.index_of(c).is_some()
+                        }))
                     }
                 };
+                number = $append(number, digit, size);
+                if number.is_none() {
+                    // Invalid characters further on are reported instead, since they are not scanned yet.
+                    if text.chars().any(|c| charset.index_of(c).is_none()) {
+                        return Err(N2NErr::invalid_in(text, charset, |_, c| {
+                            charset.index_of(c).is_some()
+                        }));
+                    }
+                    return Err(N2NErr::TooLarge {
+                        charset: Some(charset.clone()),
+                        max: <$int>::MAX as u128,
+                    });
+                }
             }
-            Ok(number)
+            number.ok_or(N2NErr::EmptyInput)
         }
     };
 }
 
-name2number_for_type!(name2number_u16, u16);
-name2number_for_type!(name2number_u32, u32);
-name2number_for_type!(name2number_u64, u64);
-name2number_for_type!(name2number_u128, u128);
+name2number_for_type!(name2number_u16, append_digit_u16, u16);
+name2number_for_type!(name2number_u32, append_digit_u32, u32);
+name2number_for_type!(name2number_u64, append_digit_u64, u64);
+name2number_for_type!(name2number_u128, append_digit_u128, u128);
 
 pub fn name2number_i16(text: impl AsRef<str>, charset: &Charset) -> Result<i16, N2NErr> {
     Ok(unsigned2signed_16(name2number_u16(text.as_ref(), charset)?))
@@ -77,11 +77,73 @@ pub fn name2number_i128(text: impl AsRef<str>, charset: &Charset) -> Result<i128
     )?))
 }
 
+/// Decode a name created by `number2name_alternating` with the same charsets.
+pub(crate) fn name2number_alternating(text: &str, charsets: &[Charset]) -> Result<u128, N2NErr> {
+    if text.is_empty() {
+        return Err(N2NErr::EmptyInput);
+    }
+    let mut number: u128 = 0;
+    // Scale of the current position; None if it does not fit, which is fine if there are no more characters.
+    let mut scale: Option<u128> = Some(1);
+    for (position, (character, charset)) in
+        text.chars().rev().zip(charsets.iter().cycle()).enumerate()
+    {
+        let value = match charset.index_of(character) {
+            Some(value) => value as u128,
+            None => {
+                // Report the charset of the first invalid character.
+                let length = text.chars().count();
+                let charset_at = |index: usize| &charsets[(length - 1 - index) % charsets.len()];
+                let first = text
+                    .chars()
+                    .enumerate()
+                    .position(|(index, c)| charset_at(index).index_of(c).is_none())
+                    .unwrap_or(length - 1 - position);
+                return Err(N2NErr::invalid_in(text, charset_at(first), |index, c| {
+                    charset_at(index).index_of(c).is_some()
+                }));
+            }
+        };
+        let digit = if position == 0 { value } else { value + 1 };
+        number = match scale
+            .and_then(|scale| digit.checked_mul(scale))
+            .and_then(|add| number.checked_add(add))
+        {
+            Some(number) => number,
+            None => {
+                return Err(N2NErr::TooLarge {
+                    charset: None,
+                    max: u128::MAX,
+                })
+            }
+        };
+        scale = scale.and_then(|scale| scale.checked_mul(charset.len() as u128));
+    }
+    Ok(number)
+}
+
 /// Convert a string encoded using the given charset back to the number it represents.
 pub fn name2number(text: impl AsRef<str>, charset: &Charset) -> Result<u64, N2NErr> {
     name2number_u64(text, charset)
 }
 
+/// Decode the name at the start of the text, which ends at the first character not in the charset.
+/// Returns the number and the length of the name in bytes, so the rest is `&text[length..]`.
+/// Fails with `TooLarge` if that whole name does not fit in the type.
+pub fn name2number_prefix<T: N2NInt>(text: &str, charset: &Charset) -> Result<(T, usize), N2NErr> {
+    let length = text
+        .char_indices()
+        .find(|&(_, character)| charset.index_of(character).is_none())
+        .map_or(text.len(), |(offset, _)| offset);
+    if length == 0 && !text.is_empty() {
+        // Only the first character is invalid, since the rest is not part of the name.
+        return Err(N2NErr::invalid_in(text, charset, |position, _| {
+            position > 0
+        }));
+    }
+    Ok((T::decode(&text[..length], charset)?, length))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -203,10 +265,7 @@ mod tests {
     fn invalid_case_insensitive_single() -> Result<(), N2NErr> {
         let charset = Charset::case_insensitive("aBcD");
         match name2number("e", &charset).unwrap_err() {
-            N2NErr::InvalidCharacter {
-                character,
-                charset: _,
-            } => assert_eq!(character, 'e'),
+            N2NErr::InvalidCharacter { character, .. } => assert_eq!(character, 'e'),
             _ => panic!("wrong error"),
         }
         Ok(())
@@ -216,10 +275,7 @@ mod tests {
     fn invalid_case_insensitive_long() -> Result<(), N2NErr> {
         let charset = Charset::case_insensitive("aBcDeFgHiJkLmNoPqRsTuVwXyZ");
         match name2number("gkgwByLwRXT7Pq", &charset).unwrap_err() {
-            N2NErr::InvalidCharacter {
-                character,
-                charset: _,
-            } => assert_eq!(character, '7'),
+            N2NErr::InvalidCharacter { character, .. } => assert_eq!(character, '7'),
             _ => panic!("wrong error"),
         }
         Ok(())
@@ -229,10 +285,7 @@ mod tests {
     fn invalid_case_sensitive_single() -> Result<(), N2NErr> {
         let charset = Charset::case_sensitive("aBcD");
         match name2number("b", &charset).unwrap_err() {
-            N2NErr::InvalidCharacter {
-                character,
-                charset: _,
-            } => assert_eq!(character, 'b'),
+            N2NErr::InvalidCharacter { character, .. } => assert_eq!(character, 'b'),
             _ => panic!("wrong error"),
         }
         Ok(())
@@ -242,10 +295,43 @@ mod tests {
     fn invalid_case_sensitive_long() -> Result<(), N2NErr> {
         let charset = Charset::case_sensitive("aBcDeFgHiJkLmNoPqRsTuVwXyZ");
         match name2number("gkgwByLwRXTlPP", &charset).unwrap_err() {
+            N2NErr::InvalidCharacter { character, .. } => assert_eq!(character, 'l'),
+            _ => panic!("wrong error"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_before_overflow() {
+        let charset = Charset::case_sensitive("aB");
+        let name = format!("x{}", "B".repeat(100));
+        match name2number_u16(name, &charset).unwrap_err() {
+            N2NErr::InvalidCharacter { position, .. } => assert_eq!(position, 0),
+            _ => panic!("wrong error"),
+        }
+    }
+
+    #[test]
+    fn prefix() -> Result<(), N2NErr> {
+        let charset = Charset::case_insensitive("abc");
+        assert_eq!(name2number_prefix::<u64>("aab/line-7", &charset)?, (13, 3));
+        assert_eq!(name2number_prefix::<u8>("AaB", &charset)?, (13, 3));
+        assert_eq!(name2number_prefix::<u8>("b€", &charset)?, (1, 1));
+        match name2number_prefix::<u64>("€abc", &charset).unwrap_err() {
             N2NErr::InvalidCharacter {
-                character,
-                charset: _,
-            } => assert_eq!(character, 'l'),
+                character, more, ..
+            } => {
+                assert_eq!(character, '€');
+                assert!(more.is_empty());
+            }
+            _ => panic!("wrong error"),
+        }
+        match name2number_prefix::<u64>("", &charset).unwrap_err() {
+            N2NErr::EmptyInput => {}
+            _ => panic!("wrong error"),
+        }
+        match name2number_prefix::<u8>("ccccc-a", &charset).unwrap_err() {
+            N2NErr::TooLarge { max, .. } => assert_eq!(max, 255),
             _ => panic!("wrong error"),
         }
         Ok(())
@@ -263,7 +349,7 @@ mod tests {
     fn too_long() -> Result<(), N2NErr> {
         let charset = Charset::case_sensitive("aBcDeFgHiJkLmNoPqRsTuVwXyZ");
         match name2number("aaaaaaaaaaaaaaa", &charset).unwrap_err() {
-            N2NErr::TooLarge { charset: _ } => {}
+            N2NErr::TooLarge { .. } => {}
             _ => panic!("wrong error"),
         }
         Ok(())
@@ -273,7 +359,18 @@ mod tests {
     fn limit_plus_one() -> Result<(), N2NErr> {
         let charset = Charset::case_sensitive("aBcDeFgHiJkLmNoPqRsTuVwXyZ");
         match name2number("gkgwByLwRXTLPq", &charset).unwrap_err() {
-            N2NErr::TooLarge { charset: _ } => {}
+            N2NErr::TooLarge { .. } => {}
+            _ => panic!("wrong error"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn leading_char_overflow() -> Result<(), N2NErr> {
+        let charset = Charset::case_sensitive("0123456789ABCDEFGHJKMNPQRSTVWXYZ");
+        assert_eq!(name2number("EYYYYYYYYYYYZ", &charset)?, u64::MAX);
+        match name2number("ZZZZZZZZZZZZZ", &charset).unwrap_err() {
+            N2NErr::TooLarge { .. } => {}
             _ => panic!("wrong error"),
         }
         Ok(())
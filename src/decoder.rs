@@ -0,0 +1,221 @@
+use ::alloc::vec::Vec;
+use ::core::marker::PhantomData;
+
+use crate::decode::append_digit_u128;
+use crate::int::N2NInt;
+use crate::typ::N2NErr;
+use crate::Charset;
+
+/// Decodes a name one character at a time, e.g. while it is being typed or received.
+///
+/// Gives the same numbers as `charset.decode::<T>`, but can report on unfinished names.
+#[derive(Debug, Clone)]
+pub struct Decoder<'a, T: N2NInt = u64> {
+    charset: &'a Charset,
+    /// The encoded number of the characters so far, if any and if it fits in a u128.
+    number: Option<u128>,
+    length: usize,
+    byte_length: usize,
+    too_large: bool,
+    target: PhantomData<T>,
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl<'a, T: N2NInt> Decoder<'a, T> {
+    pub fn new(charset: &'a Charset) -> Self {
+        Decoder {
+            charset,
+            number: None,
+            length: 0,
+            byte_length: 0,
+            too_large: false,
+            target: PhantomData,
+        }
+    }
+
+    /// Add the next character. If it is not in the charset, fails without changing the decoder.
+    pub fn push(&mut self, character: char) -> Result<(), N2NErr> {
+        let digit = match self.charset.index_of(character) {
+            Some(digit) => digit,
+            None => {
+                return Err(N2NErr::InvalidCharacter {
+                    character,
+                    position: self.length,
+                    byte_offset: self.byte_length,
+                    charset: self.charset.clone(),
+                    more: Vec::new(),
+                })
+            }
+        };
+        if !self.too_large {
+            self.number = append_digit_u128(self.number, digit, self.charset.len() as u64)
+                .filter(|&number| number <= T::ENCODED_MAX);
+            self.too_large = self.number.is_none();
+        }
+        self.length += 1;
+        self.byte_length += character.len_utf8();
+        Ok(())
+    }
+
+    /// Add all characters, stopping at the first one that is not in the charset.
+    pub fn push_str(&mut self, text: &str) -> Result<(), N2NErr> {
+        text.chars().try_for_each(|character| self.push(character))
+    }
+
+    /// The number of the characters so far, unless there are none or it is too large.
+    pub fn value(&self) -> Option<T> {
+        T::from_encoded(self.number?)
+    }
+
+    /// Whether the characters so far are past the largest number of the type, so no more characters can fix it.
+    pub fn is_too_large(&self) -> bool {
+        self.too_large
+    }
+
+    /// How many more characters can be added before the number is too large for the type.
+    /// Saturates at `usize::MAX` (only possible for charsets of one character).
+    pub fn remaining(&self) -> usize {
+        if self.too_large {
+            return 0;
+        }
+        let size = self.charset.len() as u64;
+        if size == 1 {
+            // Each character adds one, so avoid stepping through them.
+            let count = match self.number {
+                Some(number) => T::ENCODED_MAX - number,
+                None => T::ENCODED_MAX.saturating_add(1),
+            };
+            return usize::try_from(count).unwrap_or(usize::MAX);
+        }
+        let mut number = self.number;
+        let mut count = 0;
+        // Adding the first character of the charset grows the number the least.
+        while let Some(next) =
+            append_digit_u128(number, 0, size).filter(|&next| next <= T::ENCODED_MAX)
+        {
+            number = Some(next);
+            count += 1;
+        }
+        count
+    }
+
+    /// Number of characters added so far.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// The number of the characters so far, or an error if there are none or it is too large.
+    pub fn finish(&self) -> Result<T, N2NErr> {
+        if self.length == 0 {
+            return Err(N2NErr::EmptyInput);
+        }
+        self.value().ok_or_else(|| N2NErr::TooLarge {
+            charset: Some(self.charset.clone()),
+            max: T::ENCODED_MAX,
+        })
+    }
+
+    /// Remove all characters, to decode a new name.
+    pub fn clear(&mut self) {
+        *self = Decoder::new(self.charset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_as_decode() -> Result<(), N2NErr> {
+        let charset = Charset::case_insensitive("aBcD");
+        for number in (0..500).chain([u64::MAX - 1, u64::MAX]) {
+            let name = charset.encode(number);
+            let mut decoder = Decoder::<u64>::new(&charset);
+            decoder.push_str(&name.to_uppercase())?;
+            assert_eq!(decoder.finish()?, number);
+        }
+        let mut decoder = Decoder::<i32>::new(&charset);
+        decoder.push_str(&charset.encode_int(-5i32))?;
+        assert_eq!(decoder.value(), Some(-5));
+        Ok(())
+    }
+
+    #[test]
+    fn running_value() -> Result<(), N2NErr> {
+        let charset = Charset::case_sensitive("abc");
+        let mut decoder = Decoder::<u8>::new(&charset);
+        assert_eq!(decoder.value(), None);
+        assert_eq!(decoder.remaining(), 5);
+        let mut values = Vec::new();
+        for character in "aab".chars() {
+            decoder.push(character)?;
+            values.push(decoder.value());
+        }
+        assert_eq!(values, [Some(0), Some(3), Some(13)]);
+        assert_eq!(decoder.len(), 3);
+        assert_eq!(decoder.remaining(), 2);
+        decoder.clear();
+        assert_eq!(decoder.len(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn too_large() -> Result<(), N2NErr> {
+        let charset = Charset::case_sensitive("abc");
+        let mut decoder = Decoder::<u8>::new(&charset);
+        decoder.push_str(&charset.encode_int(255u8))?;
+        assert!(!decoder.is_too_large());
+        assert_eq!(decoder.remaining(), 0);
+        decoder.push('a')?;
+        assert!(decoder.is_too_large());
+        assert_eq!(decoder.value(), None);
+        match decoder.finish().unwrap_err() {
+            N2NErr::TooLarge { max, .. } => assert_eq!(max, 255),
+            _ => panic!("wrong error"),
+        }
+        let mut decoder = Decoder::<u128>::new(&charset);
+        decoder.push_str(&"c".repeat(100))?;
+        assert!(decoder.is_too_large());
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_character() {
+        let charset = Charset::case_sensitive("abc");
+        let mut decoder = Decoder::<u64>::new(&charset);
+        match decoder.push_str("ab€d").unwrap_err() {
+            N2NErr::InvalidCharacter {
+                character,
+                position,
+                byte_offset,
+                ..
+            } => {
+                assert_eq!(character, '€');
+                assert_eq!(position, 2);
+                assert_eq!(byte_offset, 2);
+            }
+            _ => panic!("wrong error"),
+        }
+        assert_eq!(decoder.value(), Some(4));
+        match decoder.finish() {
+            Ok(4) => {}
+            _ => panic!("invalid character should not change the decoder"),
+        }
+    }
+
+    #[test]
+    fn single_character_charset() -> Result<(), N2NErr> {
+        let charset = Charset::case_sensitive("a");
+        let mut decoder = Decoder::<u64>::new(&charset);
+        assert_eq!(decoder.remaining(), usize::MAX);
+        decoder.push_str("aaa")?;
+        assert_eq!(decoder.value(), Some(2));
+        assert_eq!(decoder.remaining() as u64, u64::MAX - 2);
+        let mut decoder = Decoder::<u8>::new(&charset);
+        assert_eq!(decoder.remaining(), 256);
+        decoder.push_str(&"a".repeat(256))?;
+        assert_eq!(decoder.remaining(), 0);
+        assert_eq!(decoder.value(), Some(255));
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+//! Support for the code generated by `#[derive(NameEncoded)]`; not part of the public api.
+
+use ::core::fmt;
+use ::core::marker::PhantomData;
+
+use ::serde::de::{Deserializer, Error, Visitor};
+
+use crate::typ::N2NErr;
+
+pub use ::serde;
+pub use ::std::sync::OnceLock;
+
+/// Deserialize a name from a string, without allocating if the deserializer can borrow it.
+pub fn deserialize_name<'de, D: Deserializer<'de>, T>(
+    deserializer: D,
+    from_name: fn(&str) -> Result<T, N2NErr>,
+) -> Result<T, D::Error> {
+    struct NameVisitor<T> {
+        from_name: fn(&str) -> Result<T, N2NErr>,
+        target: PhantomData<T>,
+    }
+
+    impl<T> Visitor<'_> for NameVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a name")
+        }
+
+        fn visit_str<E: Error>(self, text: &str) -> Result<T, E> {
+            (self.from_name)(text).map_err(E::custom)
+        }
+    }
+
+    deserializer.deserialize_str(NameVisitor {
+        from_name,
+        target: PhantomData,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Charset, NameEncoded, BASE32HUMAN};
+
+    #[derive(Debug, PartialEq, NameEncoded)]
+    #[n2n(charset = "BASE32HUMAN")]
+    struct OrderId(u64);
+
+    #[derive(Debug, PartialEq, NameEncoded)]
+    #[n2n(charset = "abc", case_insensitive, signed)]
+    struct Offset {
+        value: u32,
+    }
+
+    #[test]
+    fn builtin_charset() {
+        let id = OrderId(123_456);
+        let name = BASE32HUMAN.encode(123_456u64);
+        assert_eq!(id.to_name(), name);
+        assert_eq!(id.to_string(), name);
+        assert_eq!(OrderId::from_name(&name).unwrap(), id);
+        assert_eq!(name.parse::<OrderId>().unwrap(), id);
+    }
+
+    #[test]
+    fn literal_charset_and_signed() {
+        let charset = Charset::case_insensitive("abc");
+        let offset = Offset { value: u32::MAX };
+        assert_eq!(offset.to_name(), charset.encode_int(-1i32));
+        assert_eq!(offset.to_name(), "b");
+        assert_eq!("B".parse::<Offset>().unwrap(), offset);
+        assert!("d".parse::<Offset>().is_err());
+    }
+
+    #[test]
+    fn serde() {
+        let json = ::serde_json::to_string(&OrderId(13)).unwrap();
+        assert_eq!(json, format!("\"{}\"", BASE32HUMAN.encode(13u64)));
+        let id: OrderId = ::serde_json::from_str(&json).unwrap();
+        assert_eq!(id, OrderId(13));
+        assert!(::serde_json::from_str::<OrderId>("\"!\"").is_err());
+        assert!(::serde_json::from_str::<OrderId>("13").is_err());
+    }
+}
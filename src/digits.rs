@@ -0,0 +1,322 @@
+use ::alloc::vec;
+use ::alloc::vec::Vec;
+use ::core::cmp::Ordering;
+
+// Names as charset indices, most significant first, in the same bijective numbering as `encode.rs`.
+// These work for any length, so names do not need to fit in an integer type.
+
+/// A length of a name of a single-character charset, whose names have a digit for every number
+/// plus one: the number plus `extra`. Empty if it does not fit in a usize.
+fn unary_len(number: u128, extra: usize) -> Option<usize> {
+    usize::try_from(number).ok()?.checked_add(extra)
+}
+
+/// Make the name of a single-character charset this long. Fails (leaving the name unchanged)
+/// if it does not fit in memory.
+fn resize_unary(digits: &mut Vec<usize>, length: Option<usize>) -> bool {
+    let Some(length) = length else {
+        return false;
+    };
+    if digits
+        .try_reserve_exact(length.saturating_sub(digits.len()))
+        .is_err()
+    {
+        return false;
+    }
+    digits.resize(length, 0);
+    true
+}
+
+/// Indices of the name of a number.
+/// Empty for a single-character charset if the name does not fit in memory.
+pub(crate) fn from_u128(number: u128, size: usize) -> Option<Vec<usize>> {
+    if size == 1 {
+        let mut digits = Vec::new();
+        return resize_unary(&mut digits, unary_len(number, 1)).then_some(digits);
+    }
+    let size = size as u128;
+    let mut remainder = number;
+    let mut digits = Vec::new();
+    loop {
+        digits.push((remainder % size) as usize);
+        remainder /= size;
+        if remainder == 0 {
+            break;
+        }
+        remainder -= 1;
+    }
+    digits.reverse();
+    Some(digits)
+}
+
+/// The plain (not bijective) base-`size` value of the digits, if it fits in a u128.
+fn plain_value(digits: &[usize], size: usize) -> Option<u128> {
+    let mut value: u128 = 0;
+    for &digit in digits {
+        value = value
+            .checked_mul(size as u128)?
+            .checked_add(digit as u128)?;
+    }
+    Some(value)
+}
+
+/// Change the name into the next one.
+pub(crate) fn increment(digits: &mut Vec<usize>, size: usize) {
+    for digit in digits.iter_mut().rev() {
+        if *digit + 1 < size {
+            *digit += 1;
+            return;
+        }
+        *digit = 0;
+    }
+    // All digits overflowed, so the next name is the first one that is one longer.
+    digits.insert(0, 0);
+}
+
+/// Change the name into the previous one. Returns false (and leaves the name unchanged) for the first name.
+pub(crate) fn decrement(digits: &mut Vec<usize>, size: usize) -> bool {
+    if digits.len() <= 1 && digits.first().is_none_or(|&digit| digit == 0) {
+        return false;
+    }
+    for digit in digits.iter_mut().rev() {
+        if *digit > 0 {
+            *digit -= 1;
+            return true;
+        }
+        *digit = size - 1;
+    }
+    // All digits underflowed, so the previous name is the last one that is one shorter.
+    digits.remove(0);
+    true
+}
+
+/// Change the name into the one `amount` places later.
+/// Returns false (and leaves the name unchanged) for a single-character charset
+/// if the name does not fit in memory.
+pub(crate) fn add(digits: &mut Vec<usize>, amount: u128, size: usize) -> bool {
+    if size == 1 {
+        return resize_unary(digits, unary_len(amount, digits.len()));
+    }
+    let size = size as u128;
+    let mut carry = amount;
+    for digit in digits.iter_mut().rev() {
+        if carry == 0 {
+            return true;
+        }
+        let sum = *digit as u128 + carry % size;
+        *digit = (sum % size) as usize;
+        carry = carry / size + sum / size;
+    }
+    // Extra digits use the bijective (1 to size) digit values.
+    let mut extra = Vec::new();
+    while carry > 0 {
+        extra.push(((carry - 1) % size) as usize);
+        carry = (carry - 1) / size;
+    }
+    extra.reverse();
+    digits.splice(0..0, extra);
+    true
+}
+
+/// Change the name into the one `amount` places earlier.
+/// Returns false (and leaves the name unchanged) if that would be before the first name.
+pub(crate) fn sub(digits: &mut Vec<usize>, amount: u128, size: usize) -> bool {
+    if size == 1 {
+        if amount >= digits.len() as u128 {
+            return false;
+        }
+        digits.truncate(digits.len() - amount as usize);
+        return true;
+    }
+    let mut result = digits.clone();
+    let mut remaining = amount;
+    loop {
+        match plain_value(&result, size) {
+            Some(value) if value < remaining => {
+                // Move to the last name that is one shorter, which is `value + 1` places earlier.
+                if result.len() <= 1 {
+                    return false;
+                }
+                remaining -= value + 1;
+                result = vec![size - 1; result.len() - 1];
+            }
+            _ => break,
+        }
+    }
+    // The value of the digits is at least the remaining amount, so normal subtraction works.
+    let size = size as u128;
+    let mut borrow = remaining;
+    for digit in result.iter_mut().rev() {
+        if borrow == 0 {
+            break;
+        }
+        let subtract = borrow % size;
+        borrow /= size;
+        if *digit as u128 >= subtract {
+            *digit -= subtract as usize;
+        } else {
+            *digit = (*digit as u128 + size - subtract) as usize;
+            borrow += 1;
+        }
+    }
+    *digits = result;
+    true
+}
+
+/// Number of steps between two names (in either order), if it fits in a u128.
+pub(crate) fn distance(left: &[usize], right: &[usize], size: usize) -> Option<u128> {
+    let (low, high) = match cmp(left, right) {
+        Ordering::Greater => (right, left),
+        _ => (left, right),
+    };
+    if size == 1 {
+        // All names are the same character, so only the length matters.
+        return Some((high.len() - low.len()) as u128);
+    }
+    // The number plus one, in plain base-`size` with least significant first,
+    // is found by adding one to every digit.
+    let plus_one = |digits: &[usize]| {
+        let mut plain = Vec::with_capacity(digits.len() + 1);
+        let mut carry = 0;
+        for &digit in digits.iter().rev() {
+            let value = digit + 1 + carry;
+            plain.push(value % size);
+            carry = value / size;
+        }
+        plain.push(carry);
+        plain
+    };
+    let mut difference = plus_one(high);
+    let low = plus_one(low);
+    let mut borrow = 0;
+    for (position, digit) in difference.iter_mut().enumerate() {
+        let subtract = low.get(position).copied().unwrap_or(0) + borrow;
+        if *digit >= subtract {
+            *digit -= subtract;
+            borrow = 0;
+        } else {
+            *digit += size - subtract;
+            borrow = 1;
+        }
+    }
+    plain_value(&difference.into_iter().rev().collect::<Vec<usize>>(), size)
+}
+
+/// Order of names, which is the same as the order of the numbers they represent.
+pub(crate) fn cmp(left: &[usize], right: &[usize]) -> Ordering {
+    left.len().cmp(&right.len()).then_with(|| left.cmp(right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits_of(number: u128, size: usize) -> Vec<usize> {
+        from_u128(number, size).unwrap()
+    }
+
+    #[test]
+    fn conversion() {
+        assert_eq!(digits_of(0, 3), vec![0]);
+        assert_eq!(digits_of(13, 3), vec![0, 0, 1]);
+        let mut max = vec![0; 128];
+        max[127] = 1;
+        assert_eq!(digits_of(u128::MAX, 2), max);
+    }
+
+    #[test]
+    fn single_character() {
+        assert_eq!(digits_of(2, 1), vec![0; 3]);
+        let mut digits = digits_of(4, 1);
+        assert!(add(&mut digits, 1 << 20, 1));
+        assert_eq!(digits.len(), (1 << 20) + 5);
+        assert!(sub(&mut digits, 1 << 20, 1));
+        assert_eq!(digits, vec![0; 5]);
+        assert!(!sub(&mut digits, u128::MAX, 1));
+        assert_eq!(digits, vec![0; 5]);
+    }
+
+    #[test]
+    fn single_character_too_long() {
+        assert!(from_u128(u128::MAX, 1).is_none());
+        assert!(from_u128(u64::MAX as u128, 1).is_none());
+        let mut digits = digits_of(4, 1);
+        assert!(!add(&mut digits, u128::MAX, 1));
+        assert!(!add(&mut digits, usize::MAX as u128 - 4, 1));
+        assert_eq!(digits, vec![0; 5]);
+    }
+
+    #[test]
+    fn increment_decrement() {
+        let mut digits = vec![0];
+        for number in 1..200 {
+            increment(&mut digits, 3);
+            assert_eq!(digits, digits_of(number, 3));
+        }
+        for number in (0..199).rev() {
+            assert!(decrement(&mut digits, 3));
+            assert_eq!(digits, digits_of(number, 3));
+        }
+        assert!(!decrement(&mut digits, 3));
+        assert_eq!(digits, vec![0]);
+    }
+
+    #[test]
+    fn add_sub() {
+        for size in 1..5 {
+            for start in 0..50 {
+                for amount in 0..50 {
+                    let mut digits = digits_of(start, size);
+                    assert!(add(&mut digits, amount, size));
+                    assert_eq!(digits, digits_of(start + amount, size));
+                    let mut digits = digits_of(start, size);
+                    let valid = sub(&mut digits, amount, size);
+                    if amount <= start {
+                        assert!(valid);
+                        assert_eq!(digits, digits_of(start - amount, size));
+                    } else {
+                        assert!(!valid);
+                        assert_eq!(digits, digits_of(start, size));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn add_sub_large() {
+        let mut digits = digits_of(12345, 7);
+        assert!(add(&mut digits, u128::MAX - 12345, 7));
+        assert_eq!(digits, digits_of(u128::MAX, 7));
+        assert!(sub(&mut digits, u128::MAX - 1, 7));
+        assert_eq!(digits, vec![1]);
+    }
+
+    #[test]
+    fn distances() {
+        for size in 1..5 {
+            for left in 0u128..40 {
+                for right in 0..40 {
+                    let expected = left.abs_diff(right);
+                    let actual = distance(&digits_of(left, size), &digits_of(right, size), size);
+                    assert_eq!(actual, Some(expected));
+                }
+            }
+        }
+        assert_eq!(
+            distance(&digits_of(0, 3), &digits_of(u128::MAX, 3), 3),
+            Some(u128::MAX)
+        );
+        let mut beyond = digits_of(u128::MAX, 3);
+        increment(&mut beyond, 3);
+        assert_eq!(distance(&digits_of(0, 3), &beyond, 3), None);
+        assert_eq!(distance(&digits_of(1, 3), &beyond, 3), Some(u128::MAX));
+    }
+
+    #[test]
+    fn order() {
+        assert_eq!(cmp(&[2], &[0, 0]), Ordering::Less);
+        assert_eq!(cmp(&[0, 1], &[0, 0]), Ordering::Greater);
+        assert_eq!(cmp(&[1, 1], &[1, 1]), Ordering::Equal);
+    }
+}
@@ -0,0 +1,159 @@
+/// How to divide by the charset size, chosen once when the charset is created.
+///
+/// Dividing by a variable is slow, especially for u128, but for a fixed divisor it can be
+/// replaced by shifts (for powers of two) or by a multiplication.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Divisor {
+    divisor: u64,
+    strategy: Strategy,
+    chunk: Chunk,
+}
+
+/// How to split u128 numbers into chunks that fit in a u64.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Chunk {
+    /// The largest power of the divisor that is at most 2^63.
+    pub(crate) size: u64,
+    /// The exponent of `size`, which is the number of characters per chunk. Zero for a divisor of one.
+    pub(crate) len: usize,
+    /// The number of names with 1 to `len` characters, which fits since `size` is at most 2^63.
+    pub(crate) shorter: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Strategy {
+    /// Powers of two (including 1): shift right, and mask for the remainder.
+    Shift { shift: u32, mask: u64 },
+    /// Other sizes: multiply by a reciprocal (Granlund and Montgomery, 1994, figure 4.1).
+    Reciprocal { multiplier: u64, shift: u32 },
+}
+
+impl Divisor {
+    /// Panics if the divisor is zero.
+    pub(crate) fn new(divisor: u64) -> Self {
+        assert!(divisor > 0, "cannot divide by zero");
+        let strategy = if divisor.is_power_of_two() {
+            Strategy::Shift {
+                shift: divisor.trailing_zeros(),
+                mask: divisor - 1,
+            }
+        } else {
+            // The smallest power of two above the divisor is 2^log.
+            let log = u64::BITS - (divisor - 1).leading_zeros();
+            let multiplier =
+                ((((1u128 << log) - divisor as u128) << 64) / divisor as u128) as u64 + 1;
+            Strategy::Reciprocal {
+                multiplier,
+                shift: log - 1,
+            }
+        };
+        let mut chunk = Chunk {
+            size: 1,
+            len: 0,
+            shorter: 0,
+        };
+        if divisor > 1 {
+            while let Some(next) = chunk
+                .size
+                .checked_mul(divisor)
+                .filter(|&next| next <= 1 << 63)
+            {
+                chunk.size = next;
+                chunk.len += 1;
+                chunk.shorter += next;
+            }
+        }
+        Divisor {
+            divisor,
+            strategy,
+            chunk,
+        }
+    }
+
+    /// The quotient and remainder.
+    #[inline]
+    pub(crate) fn div_rem(&self, number: u64) -> (u64, u64) {
+        match self.strategy {
+            Strategy::Shift { shift, mask } => (number >> shift, number & mask),
+            Strategy::Reciprocal { multiplier, shift } => {
+                let high = ((multiplier as u128 * number as u128) >> 64) as u64;
+                let quotient = (high + ((number - high) >> 1)) >> shift;
+                (quotient, number - quotient * self.divisor)
+            }
+        }
+    }
+
+    pub(crate) fn chunk(&self) -> Chunk {
+        self.chunk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers() -> impl Iterator<Item = u64> {
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        let random = (0..2000).map(move |_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state >> (state % 64)
+        });
+        (0..300)
+            .chain([u64::MAX, u64::MAX - 1, 1 << 63, (1 << 63) - 1])
+            .chain(random)
+    }
+
+    #[test]
+    fn same_as_division() {
+        let divisors = (1..300).chain([
+            1000,
+            (1 << 32) - 1,
+            1 << 32,
+            (1 << 32) + 1,
+            (1 << 63) - 1,
+            1 << 63,
+            (1 << 63) + 1,
+            u64::MAX - 1,
+            u64::MAX,
+        ]);
+        for divisor in divisors {
+            let fast = Divisor::new(divisor);
+            for number in numbers() {
+                assert_eq!(
+                    fast.div_rem(number),
+                    (number / divisor, number % divisor),
+                    "{} / {}",
+                    number,
+                    divisor
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn strategy() {
+        assert!(matches!(
+            Divisor::new(32).strategy,
+            Strategy::Shift { shift: 5, mask: 31 }
+        ));
+        assert!(matches!(
+            Divisor::new(31).strategy,
+            Strategy::Reciprocal { .. }
+        ));
+    }
+
+    #[test]
+    fn chunk() {
+        let chunk = |divisor| {
+            let chunk = Divisor::new(divisor).chunk();
+            (chunk.size, chunk.len, chunk.shorter)
+        };
+        assert_eq!(chunk(1), (1, 0, 0));
+        assert_eq!(chunk(2), (1 << 63, 63, u64::MAX - 1));
+        assert_eq!(chunk(32), (1 << 60, 12, ((1 << 60) - 1) / 31 * 32));
+        assert_eq!(chunk(10).0, 10u64.pow(18));
+        assert_eq!(chunk(u64::MAX), (1, 0, 0));
+    }
+}
@@ -1,47 +1,285 @@
+use ::alloc::string::String;
+use ::alloc::vec::Vec;
+use ::core::fmt;
+
+use crate::int::N2NInt;
 use crate::{
     signed2unsigned_128, signed2unsigned_16, signed2unsigned_32, signed2unsigned_64, Charset,
 };
 
+/// Writes into a byte buffer, failing if it is full.
+struct SliceWriter<'a> {
+    buffer: &'a mut [u8],
+    length: usize,
+}
+
+impl fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        let end = self.length + text.len();
+        if end > self.buffer.len() {
+            return Err(fmt::Error);
+        }
+        self.buffer[self.length..end].copy_from_slice(text.as_bytes());
+        self.length = end;
+        Ok(())
+    }
+
+    fn write_char(&mut self, character: char) -> fmt::Result {
+        let end = self.length + character.len_utf8();
+        if end > self.buffer.len() {
+            return Err(fmt::Error);
+        }
+        character.encode_utf8(&mut self.buffer[self.length..end]);
+        self.length = end;
+        Ok(())
+    }
+}
+
+/// A name that is built from the end, so it can be written at once.
+struct NameBuffer {
+    /// UTF-8; with at least two characters, names are never longer than the number of bits.
+    bytes: [u8; 4 * u128::BITS as usize],
+    start: usize,
+}
+
+impl NameBuffer {
+    fn new() -> Self {
+        NameBuffer {
+            bytes: [0; 4 * u128::BITS as usize],
+            start: 4 * u128::BITS as usize,
+        }
+    }
+
+    #[inline]
+    fn push_front(&mut self, character: char) {
+        self.start -= character.len_utf8();
+        character.encode_utf8(&mut self.bytes[self.start..]);
+    }
+
+    fn write_to(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str(
+            ::core::str::from_utf8(&self.bytes[self.start..]).expect("name is valid UTF-8"),
+        )
+    }
+}
+
+/// Write a name of only one character, which is repeated.
+fn write_repeated(number: u128, charset: &Charset, out: &mut impl fmt::Write) -> fmt::Result {
+    for _ in 0..=number {
+        out.write_char(charset[0])?;
+    }
+    Ok(())
+}
+
+/// Add the characters of the name of the number to the front, using the division strategy of the charset.
+#[inline]
+fn push_name(mut remainder: u64, charset: &Charset, name: &mut NameBuffer) {
+    let divisor = charset.divisor();
+    loop {
+        let (quotient, digit) = divisor.div_rem(remainder);
+        name.push_front(charset[digit as usize]);
+        if quotient == 0 {
+            break;
+        }
+        // Like base-n, but each earlier character is worth one more, so there are no leading zeros.
+        remainder = quotient - 1;
+    }
+}
+
+fn write_name_u64(number: u64, charset: &Charset, out: &mut impl fmt::Write) -> fmt::Result {
+    if charset.len() == 1 {
+        return write_repeated(number as u128, charset, out);
+    }
+    let mut name = NameBuffer::new();
+    push_name(number, charset, &mut name);
+    name.write_to(out)
+}
+
+/// Like `write_name_u64`, but the last characters of large numbers are split off in chunks that fit
+/// in a u64, so that only one slow u128 division is needed per chunk.
+fn write_name_u128(mut number: u128, charset: &Charset, out: &mut impl fmt::Write) -> fmt::Result {
+    if charset.len() == 1 {
+        return write_repeated(number, charset, out);
+    }
+    let divisor = charset.divisor();
+    let chunk = divisor.chunk();
+    let mut name = NameBuffer::new();
+    while number > u64::MAX as u128 {
+        // The name has more than `chunk.len` characters. Taking that many characters off the end
+        // is like plain base-n after subtracting the shorter names.
+        let rest = number - chunk.shorter as u128;
+        let mut digits = (rest % chunk.size as u128) as u64;
+        for _ in 0..chunk.len {
+            let (quotient, digit) = divisor.div_rem(digits);
+            name.push_front(charset[digit as usize]);
+            digits = quotient;
+        }
+        number = rest / chunk.size as u128;
+    }
+    push_name(number as u64, charset, &mut name);
+    name.write_to(out)
+}
+
 macro_rules! number2name_for_type {
-    ($name: ident, $int:ty) => {
+    ($name: ident, $into_name: ident, $slice_name: ident, $int:ty, $write: ident) => {
         /// Convert a number to a short string representation using the given character set.
         pub fn $name(number: impl Into<$int>, charset: &Charset) -> String {
-            let size = charset.len() as $int;
-            let mut remainder = number.into();
-            let mut name = Vec::new();
-            loop {
-                let index = remainder % size;
-                name.push(index as usize);
-                remainder /= size;
-                if remainder == 0 {
-                    break;
-                }
-                remainder -= 1;
-            }
-            name.into_iter().map(|index| charset[index]).rev().collect()
+            let mut name = String::new();
+            $into_name(number, charset, &mut name).expect("writing to a String does not fail");
+            name
+        }
+
+        /// Write the name of a number, without allocating.
+        pub fn $into_name(
+            number: impl Into<$int>,
+            charset: &Charset,
+            out: &mut impl fmt::Write,
+        ) -> fmt::Result {
+            let number: $int = number.into();
+            $write(number.into(), charset, out)
+        }
+
+        /// Write the name of a number into the buffer as UTF-8, without allocating,
+        /// and return the number of bytes written. Panics if the buffer is too small.
+        pub fn $slice_name(number: impl Into<$int>, charset: &Charset, buffer: &mut [u8]) -> usize {
+            let mut writer = SliceWriter { buffer, length: 0 };
+            $into_name(number, charset, &mut writer).expect("buffer is too small for the name");
+            writer.length
         }
     };
 }
 
-number2name_for_type!(number2name_u16, u16);
-number2name_for_type!(number2name_u32, u32);
-number2name_for_type!(number2name_u64, u64);
-number2name_for_type!(number2name_u128, u128);
+number2name_for_type!(
+    number2name_u16,
+    number2name_into_u16,
+    number2name_to_slice_u16,
+    u16,
+    write_name_u64
+);
+number2name_for_type!(
+    number2name_u32,
+    number2name_into_u32,
+    number2name_to_slice_u32,
+    u32,
+    write_name_u64
+);
+number2name_for_type!(
+    number2name_u64,
+    number2name_into_u64,
+    number2name_to_slice_u64,
+    u64,
+    write_name_u64
+);
+number2name_for_type!(
+    number2name_u128,
+    number2name_into_u128,
+    number2name_to_slice_u128,
+    u128,
+    write_name_u128
+);
+
+macro_rules! number2name_signed_for_type {
+    ($name: ident, $into_name: ident, $slice_name: ident, $int:ty, $unsigned_name: ident, $unsigned_into_name: ident, $unsigned_slice_name: ident, $to_unsigned: ident) => {
+        pub fn $name(number: impl Into<$int>, charset: &Charset) -> String {
+            $unsigned_name($to_unsigned(number.into()), charset)
+        }
+
+        pub fn $into_name(
+            number: impl Into<$int>,
+            charset: &Charset,
+            out: &mut impl fmt::Write,
+        ) -> fmt::Result {
+            $unsigned_into_name($to_unsigned(number.into()), charset, out)
+        }
+
+        pub fn $slice_name(number: impl Into<$int>, charset: &Charset, buffer: &mut [u8]) -> usize {
+            $unsigned_slice_name($to_unsigned(number.into()), charset, buffer)
+        }
+    };
+}
 
-pub fn number2name_i16(number: impl Into<i16>, charset: &Charset) -> String {
-    number2name_u16(signed2unsigned_16(number.into()), charset)
+number2name_signed_for_type!(
+    number2name_i16,
+    number2name_into_i16,
+    number2name_to_slice_i16,
+    i16,
+    number2name_u16,
+    number2name_into_u16,
+    number2name_to_slice_u16,
+    signed2unsigned_16
+);
+number2name_signed_for_type!(
+    number2name_i32,
+    number2name_into_i32,
+    number2name_to_slice_i32,
+    i32,
+    number2name_u32,
+    number2name_into_u32,
+    number2name_to_slice_u32,
+    signed2unsigned_32
+);
+number2name_signed_for_type!(
+    number2name_i64,
+    number2name_into_i64,
+    number2name_to_slice_i64,
+    i64,
+    number2name_u64,
+    number2name_into_u64,
+    number2name_to_slice_u64,
+    signed2unsigned_64
+);
+number2name_signed_for_type!(
+    number2name_i128,
+    number2name_into_i128,
+    number2name_to_slice_i128,
+    i128,
+    number2name_u128,
+    number2name_into_u128,
+    number2name_to_slice_u128,
+    signed2unsigned_128
+);
+
+/// Like `number2name_into`, but writes into a byte buffer. Panics if the buffer is too small.
+pub(crate) fn number2name_to_slice<T: N2NInt>(
+    number: T,
+    charset: &Charset,
+    buffer: &mut [u8],
+) -> usize {
+    let mut writer = SliceWriter { buffer, length: 0 };
+    number
+        .encode_into(charset, &mut writer)
+        .expect("buffer is too small for the name");
+    writer.length
 }
 
-pub fn number2name_i32(number: impl Into<i32>, charset: &Charset) -> String {
-    number2name_u32(signed2unsigned_32(number.into()), charset)
+/// Formats as the name of a number, without allocating. Created by `Charset::display`.
+#[derive(Debug, Clone, Copy)]
+pub struct NameDisplay<'a, T: N2NInt> {
+    pub(crate) number: T,
+    pub(crate) charset: &'a Charset,
 }
 
-pub fn number2name_i64(number: impl Into<i64>, charset: &Charset) -> String {
-    number2name_u64(signed2unsigned_64(number.into()), charset)
+impl<T: N2NInt> fmt::Display for NameDisplay<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.number.encode_into(self.charset, f)
+    }
 }
 
-pub fn number2name_i128(number: impl Into<i128>, charset: &Charset) -> String {
-    number2name_u128(signed2unsigned_128(number.into()), charset)
+/// Like `number2name_u128`, but the character at each position comes from the next charset in turn,
+/// starting with the first charset for the last character.
+pub(crate) fn number2name_alternating(number: u128, charsets: &[Charset]) -> String {
+    let mut remainder = number;
+    let mut name = Vec::new();
+    for charset in charsets.iter().cycle() {
+        let size = charset.len() as u128;
+        name.push(charset[(remainder % size) as usize]);
+        remainder /= size;
+        if remainder == 0 {
+            break;
+        }
+        remainder -= 1;
+    }
+    name.into_iter().rev().collect()
 }
 
 /// Convert a number to a short string representation using the given character set.
@@ -302,4 +540,99 @@ mod type_u128 {
         let text = number2name_i128(std::i128::MIN, &charset);
         assert_eq!(text, "BcgDeNLqRqwDsLRugsNLBTmFiJaV");
     }
+
+    #[test]
+    fn chunks_same_as_division() {
+        let data = "一二三四五六七八九十鼠牛虎兔龍蛇马羊猴鸡狗猪凤北东南西中左右上下abcdefghijklmnopqrstuvwxyzABCDEFGH";
+        let mut state = 0x2545_F491_4F6C_DD1Du128;
+        for size in [2, 3, 10, 16, 31, 32, 36, 64] {
+            let charset = Charset::case_sensitive(data.chars().take(size).collect::<String>());
+            for _ in 0..200 {
+                state = state
+                    .wrapping_mul(0x2360_ED05_1FC6_5DA4_4385_DF64_9FCC_F645)
+                    .wrapping_add(1);
+                for number in [
+                    state,
+                    state >> 32,
+                    state >> 60,
+                    u64::MAX as u128 + (state >> 64),
+                ] {
+                    let expected =
+                        number2name_alternating(number, ::core::slice::from_ref(&charset));
+                    assert_eq!(number2name_u128(number, &charset), expected);
+                }
+            }
+            for number in [u64::MAX as u128, u64::MAX as u128 + 1, u128::MAX] {
+                let expected = number2name_alternating(number, ::core::slice::from_ref(&charset));
+                assert_eq!(number2name_u128(number, &charset), expected);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod without_allocation {
+    use super::*;
+    use crate::Charset;
+
+    #[test]
+    fn into_matches_encode() {
+        let charset = Charset::case_sensitive("aBcDeFgHiJkLmNoPqRsTuVwXyZ");
+        let mut name = String::new();
+        for number in (0..1000).chain([u64::MAX - 1, u64::MAX]) {
+            name.clear();
+            number2name_into_u64(number, &charset, &mut name).unwrap();
+            assert_eq!(name, number2name_u64(number, &charset));
+        }
+        name.clear();
+        number2name_into_i32(-1234, &charset, &mut name).unwrap();
+        assert_eq!(name, number2name_i32(-1234, &charset));
+    }
+
+    #[test]
+    fn longest_names() {
+        let charset = Charset::case_sensitive("01");
+        let mut name = String::new();
+        number2name_into_u128(u128::MAX, &charset, &mut name).unwrap();
+        assert_eq!(name.len(), 128);
+        name.clear();
+        number2name_into_u16(u16::MAX, &charset, &mut name).unwrap();
+        assert_eq!(name.len(), 16);
+        let unary = Charset::case_sensitive("a");
+        assert_eq!(number2name_u16(4u16, &unary), "aaaaa");
+    }
+
+    #[test]
+    fn to_slice() {
+        let charset = Charset::case_sensitive("一二三四五六七八九十");
+        let mut buffer = [0u8; 16];
+        let length = number2name_to_slice_u32(123u32, &charset, &mut buffer);
+        assert_eq!(length, 9);
+        assert_eq!(
+            ::core::str::from_utf8(&buffer[..length]).unwrap(),
+            number2name_u32(123u32, &charset)
+        );
+        let length = number2name_to_slice(u128::MAX, &charset, &mut [0u8; 128]);
+        assert_eq!(length, number2name_u128(u128::MAX, &charset).len());
+    }
+
+    #[test]
+    #[should_panic(expected = "buffer is too small")]
+    fn to_slice_too_small() {
+        let charset = Charset::case_sensitive("abc");
+        number2name_to_slice_u64(1000u64, &charset, &mut [0u8; 3]);
+    }
+
+    #[test]
+    fn display() {
+        let charset = Charset::case_sensitive("abc");
+        assert_eq!(format!("id-{}", charset.display(13u8)), "id-aab");
+        assert_eq!(
+            charset.display(u128::MAX).to_string(),
+            number2name_u128(u128::MAX, &charset)
+        );
+        let mut buffer = [0u8; 8];
+        let length = charset.encode_to_slice(13u64, &mut buffer);
+        assert_eq!(&buffer[..length], b"aab");
+    }
 }
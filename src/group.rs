@@ -0,0 +1,275 @@
+use ::alloc::string::String;
+use ::alloc::vec;
+use ::alloc::vec::Vec;
+
+use crate::typ::N2NErr;
+use crate::util::trimmed_range;
+use crate::Charset;
+
+/// Which end of a name groups are counted from; the group at the other end may be shorter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupFrom {
+    Left,
+    Right,
+}
+
+/// Formatting of names in groups of characters, like `ABCD-EFGH`, and parsing them back.
+///
+/// Separators (including accepted ones) cannot be characters of the charset that is used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grouping {
+    size: usize,
+    separator: char,
+    accepted: Vec<char>,
+    from: GroupFrom,
+    strict: bool,
+}
+
+impl Grouping {
+    /// Groups of `size` characters counted from the left, joined by `separator`. Panics if `size` is zero.
+    pub fn new(size: usize, separator: char) -> Self {
+        assert!(size > 0, "group size must be at least one");
+        Grouping {
+            size,
+            separator,
+            accepted: vec![separator],
+            from: GroupFrom::Left,
+            strict: false,
+        }
+    }
+
+    /// Count groups from the given end.
+    pub fn from_end(mut self, from: GroupFrom) -> Self {
+        self.from = from;
+        self
+    }
+
+    /// When parsing, only allow separators exactly where `format` would put them (or no separators at all).
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// When parsing, also remove this separator (e.g. a space besides a dash).
+    pub fn also_accept(mut self, separator: char) -> Self {
+        if !self.accepted.contains(&separator) {
+            self.accepted.push(separator);
+        }
+        self
+    }
+
+    pub fn separator(&self) -> char {
+        self.separator
+    }
+
+    fn is_separator(&self, character: char) -> bool {
+        self.accepted.contains(&character)
+    }
+
+    /// Whether a separator belongs before the character at `position` in a name of `length` characters.
+    fn is_boundary(&self, position: usize, length: usize) -> bool {
+        position > 0
+            && match self.from {
+                GroupFrom::Left => position.is_multiple_of(self.size),
+                GroupFrom::Right => (length - position).is_multiple_of(self.size),
+            }
+    }
+
+    /// Insert separators into a name.
+    pub fn format(&self, name: impl AsRef<str>) -> String {
+        let name = name.as_ref();
+        let length = name.chars().count();
+        let mut grouped = String::with_capacity(name.len() + length / self.size);
+        for (position, character) in name.chars().enumerate() {
+            if self.is_boundary(position, length) {
+                grouped.push(self.separator);
+            }
+            grouped.push(character);
+        }
+        grouped
+    }
+
+    /// Panics if any separator, including accepted ones, is a character of the charset.
+    pub(crate) fn assert_separators(&self, charset: &Charset) {
+        if let Some(separator) = self
+            .accepted
+            .iter()
+            .find(|&&c| charset.index_of(c).is_some())
+        {
+            panic!(
+                "separator '{}' cannot be used because it is part of the charset",
+                separator
+            );
+        }
+    }
+
+    /// Remove surrounding whitespace and separators, so the name can be decoded.
+    /// If strict, fails with `InvalidGrouping` for separators that are missing or in the wrong place,
+    /// unless there are characters that are not in the charset (`InvalidCharacter`).
+    /// Panics if a separator is part of the charset.
+    pub fn strip(&self, text: impl AsRef<str>, charset: &Charset) -> Result<String, N2NErr> {
+        self.assert_separators(charset);
+        let original = text.as_ref();
+        let text = original.trim();
+        let name: String = text.chars().filter(|&c| !self.is_separator(c)).collect();
+        if self.strict && name.len() != text.len() {
+            let length = name.chars().count();
+            // Whether each character is a separator in the wrong place, or misses one before it.
+            let mut misplaced = Vec::with_capacity(text.len());
+            let mut position = 0;
+            let mut previous_separator = true;
+            for character in text.chars() {
+                if self.is_separator(character) {
+                    misplaced.push(previous_separator || !self.is_boundary(position, length));
+                    previous_separator = true;
+                } else {
+                    misplaced.push(self.is_boundary(position, length) && !previous_separator);
+                    position += 1;
+                    previous_separator = false;
+                }
+            }
+            if previous_separator {
+                if let Some(last) = misplaced.last_mut() {
+                    *last = true;
+                }
+            }
+            if let Some(index) = misplaced.iter().position(|&wrong| wrong) {
+                if text
+                    .chars()
+                    .any(|c| !self.is_separator(c) && charset.index_of(c).is_none())
+                {
+                    return Err(self.invalid_in(original, charset));
+                }
+                let (start, _) = trimmed_range(original);
+                let (byte_offset, _) = original
+                    .char_indices()
+                    .nth(start + index)
+                    .expect("misplaced character is in the text");
+                return Err(N2NErr::InvalidGrouping {
+                    position: start + index,
+                    byte_offset,
+                });
+            }
+        }
+        Ok(name)
+    }
+
+    /// The error for characters of grouped text that are neither in the charset nor separators.
+    pub(crate) fn invalid_in(&self, text: &str, charset: &Charset) -> N2NErr {
+        let (start, end) = trimmed_range(text);
+        N2NErr::invalid_in(text, charset, |index, c| {
+            index < start || index >= end || self.is_separator(c) || charset.index_of(c).is_some()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn charset() -> Charset {
+        Charset::case_insensitive("0123456789ABCDEFGHJKMNPQRSTVWXYZ")
+    }
+
+    #[test]
+    fn format_left() {
+        let grouping = Grouping::new(4, '-');
+        assert_eq!(grouping.format("ABCDEFGHJK"), "ABCD-EFGH-JK");
+        assert_eq!(grouping.format("ABCDEFGH"), "ABCD-EFGH");
+        assert_eq!(grouping.format("ABC"), "ABC");
+        assert_eq!(grouping.format(""), "");
+    }
+
+    #[test]
+    fn format_right() {
+        let grouping = Grouping::new(3, ' ').from_end(GroupFrom::Right);
+        assert_eq!(grouping.format("1234567"), "1 234 567");
+        assert_eq!(grouping.format("123456"), "123 456");
+    }
+
+    #[test]
+    fn format_unicode() {
+        let grouping = Grouping::new(2, '·');
+        assert_eq!(grouping.format("一二三四五"), "一二·三四·五");
+    }
+
+    #[test]
+    fn strip_lenient() -> Result<(), N2NErr> {
+        let grouping = Grouping::new(4, '-').also_accept(' ');
+        assert_eq!(
+            grouping.strip("  ABCD-EFGH-JK \n", &charset())?,
+            "ABCDEFGHJK"
+        );
+        assert_eq!(grouping.strip("AB-CD EF--GH", &charset())?, "ABCDEFGH");
+        Ok(())
+    }
+
+    #[test]
+    fn strip_strict() -> Result<(), N2NErr> {
+        let grouping = Grouping::new(4, '-').from_end(GroupFrom::Right).strict();
+        assert_eq!(grouping.strip(" AB-CDEF-GHJK", &charset())?, "ABCDEFGHJK");
+        assert_eq!(grouping.strip("ABCDEFGHJK", &charset())?, "ABCDEFGHJK");
+        for invalid in [
+            "ABC-DEF-GHJK",
+            "AB--CDEF-GHJK",
+            "-AB-CDEF-GHJK",
+            "AB-CDEF-GHJK-",
+            "AB-CDEFGHJK",
+        ] {
+            match grouping.strip(invalid, &charset()).unwrap_err() {
+                N2NErr::InvalidGrouping { .. } => {}
+                _ => panic!("wrong error"),
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_positions() {
+        let position = |err: N2NErr| match err {
+            N2NErr::InvalidGrouping {
+                position,
+                byte_offset,
+            } => (position, byte_offset),
+            _ => panic!("wrong error"),
+        };
+        let grouping = Grouping::new(4, '-').from_end(GroupFrom::Right).strict();
+        let err = grouping.strip("  ABC-DEF-GHJK", &charset()).unwrap_err();
+        assert_eq!(position(err), (4, 4));
+        let err = grouping.strip("AB--CDEF-GHJK-", &charset()).unwrap_err();
+        assert_eq!(position(err), (3, 3));
+        let dotted = Grouping::new(4, '·').from_end(GroupFrom::Right).strict();
+        let err = dotted.strip("AB·CDEFGHJK", &charset()).unwrap_err();
+        assert_eq!(position(err), (7, 8));
+        let err = charset()
+            .decode_grouped(" ABC-DIF-GHJU", &grouping)
+            .unwrap_err();
+        let positions: Vec<usize> = err
+            .invalid_characters()
+            .iter()
+            .map(|invalid| invalid.position)
+            .collect();
+        assert_eq!(positions, [6, 12]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn accepted_separator_in_charset() {
+        let grouping = Grouping::new(4, '-').also_accept('A');
+        let _ = grouping.strip("ABCD-EFGH", &charset());
+    }
+
+    #[test]
+    fn roundtrip() -> Result<(), N2NErr> {
+        let charset = charset();
+        let grouping = Grouping::new(4, '-').strict();
+        let name = charset.encode_grouped(u64::MAX, &grouping);
+        assert_eq!(name, "EYYY-YYYY-YYYY-Z");
+        assert_eq!(charset.decode_grouped(&name, &grouping)?, u64::MAX);
+        assert_eq!(
+            charset.decode_grouped("eyyyyyyy-yyyyz", &Grouping::new(4, '-'))?,
+            u64::MAX
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+use ::core::cmp::Ordering;
+use ::core::fmt;
+use ::core::hash::{Hash, Hasher};
+use ::core::marker::PhantomData;
+use ::core::str::FromStr;
+
+use crate::int::N2NInt;
+use crate::typ::N2NErr;
+use crate::Charset;
+
+/// The kind of entity that an `Id` is for, like users or orders, with how its ids are written.
+pub trait IdKind {
+    /// Text before the name, like `usr_`.
+    const PREFIX: &'static str;
+
+    /// The number type of the ids, like `u64`.
+    type Int: N2NInt + Ord + Hash + fmt::Debug;
+
+    /// The charset of the names after the prefix.
+    fn charset() -> &'static Charset;
+}
+
+/// An id for a specific kind of entity, written as prefix and name, like `usr_ab3k`.
+///
+/// Ids of different kinds are different types, so they cannot be mixed up. They are ordered by number.
+pub struct Id<K: IdKind> {
+    number: K::Int,
+    kind: PhantomData<fn() -> K>,
+}
+
+impl<K: IdKind> Id<K> {
+    pub fn new(number: K::Int) -> Self {
+        Id {
+            number,
+            kind: PhantomData,
+        }
+    }
+
+    pub fn number(&self) -> K::Int {
+        self.number
+    }
+}
+
+impl<K: IdKind> fmt::Display for Id<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(K::PREFIX)?;
+        self.number.encode_into(K::charset(), f)
+    }
+}
+
+impl<K: IdKind> fmt::Debug for Id<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Id({}, {:?})", self, self.number)
+    }
+}
+
+/// Parses ids with the prefix of the kind; fails with `InvalidPrefix` for any other prefix.
+impl<K: IdKind> FromStr for Id<K> {
+    type Err = N2NErr;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.strip_prefix(K::PREFIX) {
+            Some(name) => K::Int::decode(name, K::charset())
+                .map(Id::new)
+                .map_err(|err| err.with_offset(K::PREFIX.chars().count(), K::PREFIX.len())),
+            None => Err(N2NErr::InvalidPrefix {
+                expected: K::PREFIX,
+            }),
+        }
+    }
+}
+
+// Implemented by hand, because deriving would require the kind to implement them too.
+
+impl<K: IdKind> Clone for Id<K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: IdKind> Copy for Id<K> {}
+
+impl<K: IdKind> PartialEq for Id<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.number == other.number
+    }
+}
+
+impl<K: IdKind> Eq for Id<K> {}
+
+impl<K: IdKind> PartialOrd for Id<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: IdKind> Ord for Id<K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.number.cmp(&other.number)
+    }
+}
+
+impl<K: IdKind> Hash for Id<K> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.number.hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::std::collections::HashSet;
+    use ::std::string::ToString;
+    use ::std::sync::OnceLock;
+
+    use super::*;
+
+    fn charset() -> &'static Charset {
+        static CHARSET: OnceLock<Charset> = OnceLock::new();
+        CHARSET.get_or_init(|| Charset::case_insensitive("abcdefghjkmnpqrstuvwxyz23456789"))
+    }
+
+    struct User;
+
+    impl IdKind for User {
+        const PREFIX: &'static str = "usr_";
+        type Int = u64;
+
+        fn charset() -> &'static Charset {
+            charset()
+        }
+    }
+
+    struct Order;
+
+    impl IdKind for Order {
+        const PREFIX: &'static str = "ord_";
+        type Int = u32;
+
+        fn charset() -> &'static Charset {
+            charset()
+        }
+    }
+
+    #[test]
+    fn roundtrip() -> Result<(), N2NErr> {
+        let id = Id::<User>::new(123_456);
+        let text = id.to_string();
+        assert_eq!(text, format!("usr_{}", charset().encode(123_456u64)));
+        assert_eq!(text.parse::<Id<User>>()?, id);
+        assert_eq!("usr_AB3K".parse::<Id<User>>()?, "usr_ab3k".parse()?);
+        assert_eq!(
+            format!("{:?}", Id::<Order>::new(0)),
+            "Id(ord_a, 0)".to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn wrong_prefix() {
+        let order = Id::<Order>::new(5).to_string();
+        match order.parse::<Id<User>>().unwrap_err() {
+            N2NErr::InvalidPrefix { expected } => assert_eq!(expected, "usr_"),
+            _ => panic!("wrong error"),
+        }
+        match "ab3k".parse::<Id<User>>().unwrap_err() {
+            N2NErr::InvalidPrefix { .. } => {}
+            _ => panic!("wrong error"),
+        }
+        match "ord_".parse::<Id<Order>>().unwrap_err() {
+            N2NErr::EmptyInput => {}
+            _ => panic!("wrong error"),
+        }
+        match "ord_ab!".parse::<Id<Order>>().unwrap_err() {
+            N2NErr::InvalidCharacter { position, .. } => assert_eq!(position, 6),
+            _ => panic!("wrong error"),
+        }
+    }
+
+    #[test]
+    fn order_and_hash() {
+        let mut ids: Vec<Id<User>> = [30, 1, 500, 2].into_iter().map(Id::new).collect();
+        ids.sort();
+        assert_eq!(
+            ids.iter().map(Id::number).collect::<Vec<_>>(),
+            [1, 2, 30, 500]
+        );
+        let unique: HashSet<Id<User>> = ids.iter().chain(&ids).copied().collect();
+        assert_eq!(unique.len(), 4);
+    }
+}
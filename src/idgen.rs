@@ -0,0 +1,622 @@
+use ::std::collections::hash_map::RandomState;
+use ::std::hash::{BuildHasher, Hasher};
+use ::std::sync::Mutex;
+use ::std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::typ::N2NErr;
+use crate::Charset;
+
+/// Maximum number of bits in an id, chosen such that fixed-width names always fit in a u128.
+const MAX_ID_BITS: u32 = 126;
+
+/// Source of the current time, in milliseconds since the unix epoch.
+/// Can be replaced to make id generation deterministic in tests.
+pub trait Clock {
+    fn now_millis(&self) -> u64;
+}
+
+/// The real system clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_millis(&self) -> u64 {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_millis() as u64,
+            Err(_) => 0,
+        }
+    }
+}
+
+impl<F: Fn() -> u64> Clock for F {
+    fn now_millis(&self) -> u64 {
+        self()
+    }
+}
+
+/// How the lowest bits of an id are filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tail {
+    /// A counter that starts at zero every millisecond (like Snowflake).
+    Sequence,
+    /// A random value every millisecond, incremented for ids within the same millisecond (like monotonic ULID).
+    /// The randomness is not cryptographically secure.
+    Random,
+}
+
+/// The components of an id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdParts {
+    /// Milliseconds since the unix epoch (not since the layout epoch).
+    pub timestamp: u64,
+    pub node: u64,
+    pub tail: u128,
+}
+
+/// Bit layout of an id: from most to least significant, a timestamp, a node/shard and a tail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdLayout {
+    timestamp_bits: u32,
+    node_bits: u32,
+    tail_bits: u32,
+    epoch_millis: u64,
+}
+
+impl IdLayout {
+    /// Panics if the layout is invalid, see `try_new`.
+    pub fn new(timestamp_bits: u32, node_bits: u32, tail_bits: u32) -> Self {
+        match IdLayout::try_new(timestamp_bits, node_bits, tail_bits) {
+            Some(layout) => layout,
+            None => panic!(
+                "invalid id layout: timestamp must be 1-64 bits, node at most 64 bits, \
+                tail at least 1 bit, and {} bits total at most",
+                MAX_ID_BITS
+            ),
+        }
+    }
+
+    /// Empty if the timestamp is not 1-64 bits, the node is over 64 bits,
+    /// the tail is empty or if there are more than 126 bits in total.
+    pub fn try_new(timestamp_bits: u32, node_bits: u32, tail_bits: u32) -> Option<Self> {
+        if timestamp_bits == 0 || timestamp_bits > 64 || node_bits > 64 || tail_bits == 0 {
+            return None;
+        }
+        if timestamp_bits + node_bits + tail_bits > MAX_ID_BITS {
+            return None;
+        }
+        Some(IdLayout {
+            timestamp_bits,
+            node_bits,
+            tail_bits,
+            epoch_millis: 0,
+        })
+    }
+
+    /// Snowflake layout: 41 bits timestamp since 2010-11-04, 10 bits node and 12 bits sequence.
+    pub fn snowflake() -> Self {
+        IdLayout::new(41, 10, 12).with_epoch(1_288_834_974_657)
+    }
+
+    /// Layout similar to ULID: 48 bits timestamp and 78 bits random (instead of 80, to fit fixed-width names).
+    pub fn ulid_like() -> Self {
+        IdLayout::new(48, 0, 78)
+    }
+
+    /// Count timestamps from this moment (milliseconds since the unix epoch) instead of from the unix epoch.
+    /// Panics if the layout is invalid with this epoch, see `try_with_epoch`.
+    pub fn with_epoch(self, epoch_millis: u64) -> Self {
+        match self.try_with_epoch(epoch_millis) {
+            Some(layout) => layout,
+            None => panic!(
+                "invalid id layout: the epoch {} plus the largest timestamp of {} bits does not fit in a u64",
+                epoch_millis, self.timestamp_bits
+            ),
+        }
+    }
+
+    /// Empty if the epoch plus the largest timestamp does not fit in a u64,
+    /// since the timestamps of ids could then not be split off.
+    pub fn try_with_epoch(mut self, epoch_millis: u64) -> Option<Self> {
+        self.timestamp_max().checked_add(epoch_millis)?;
+        self.epoch_millis = epoch_millis;
+        Some(self)
+    }
+
+    pub fn total_bits(&self) -> u32 {
+        self.timestamp_bits + self.node_bits + self.tail_bits
+    }
+
+    fn timestamp_max(&self) -> u64 {
+        mask(self.timestamp_bits) as u64
+    }
+
+    fn node_max(&self) -> u64 {
+        mask(self.node_bits) as u64
+    }
+
+    fn tail_max(&self) -> u128 {
+        mask(self.tail_bits)
+    }
+
+    /// Combine the components into an id. Empty if a component does not fit in its bits.
+    pub fn compose(&self, parts: IdParts) -> Option<u128> {
+        let timestamp = parts.timestamp.checked_sub(self.epoch_millis)?;
+        if timestamp > self.timestamp_max()
+            || parts.node > self.node_max()
+            || parts.tail > self.tail_max()
+        {
+            return None;
+        }
+        Some(
+            ((timestamp as u128) << (self.node_bits + self.tail_bits))
+                | ((parts.node as u128) << self.tail_bits)
+                | parts.tail,
+        )
+    }
+
+    /// Split an id into its components. Bits beyond the layout are ignored.
+    /// The timestamp cannot overflow, since `try_with_epoch` rejects such epochs.
+    pub fn split(&self, id: u128) -> IdParts {
+        IdParts {
+            timestamp: ((id >> (self.node_bits + self.tail_bits)) as u64 & self.timestamp_max())
+                + self.epoch_millis,
+            node: (id >> self.tail_bits) as u64 & self.node_max(),
+            tail: id & self.tail_max(),
+        }
+    }
+
+    /// Encode an id as a name. All ids of a layout have names of the same length,
+    /// so names sort like ids if the charset is in ascending order (e.g. HEX or BASE32CROCKFORD).
+    /// Panics if the charset has a single character, since fixed-width names are impossible then.
+    pub fn encode(&self, id: u128, charset: &Charset) -> String {
+        match self.try_encode(id, charset) {
+            Ok(name) => name,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Like `encode`, but fails with `SingleCharacter` instead of panicking.
+    pub fn try_encode(&self, id: u128, charset: &Charset) -> Result<String, N2NErr> {
+        let (_, offset) = self.fixed_width(charset)?;
+        Ok(charset.encode_u128(offset + (id & mask(self.total_bits()))))
+    }
+
+    /// Decode a name created by `encode` back into its components.
+    pub fn decode(&self, text: impl AsRef<str>, charset: &Charset) -> Result<IdParts, N2NErr> {
+        let text = text.as_ref();
+        let (length, offset) = self.fixed_width(charset)?;
+        let actual = text.chars().count();
+        if actual != length {
+            return Err(N2NErr::InvalidLength {
+                expected: length,
+                actual,
+            });
+        }
+        let max = offset.saturating_add(mask(self.total_bits()));
+        let value = charset.decode_u128(text).map_err(|err| err.with_max(max))?;
+        if value > max {
+            return Err(N2NErr::TooLarge {
+                charset: Some(charset.clone()),
+                max,
+            });
+        }
+        let value = value - offset;
+        Ok(self.split(value))
+    }
+
+    /// The length of names, and the number that is encoded as the first name of that length.
+    fn fixed_width(&self, charset: &Charset) -> Result<(usize, u128), N2NErr> {
+        if charset.len() == 1 {
+            return Err(N2NErr::SingleCharacter {
+                charset: charset.clone(),
+            });
+        }
+        let size = charset.len() as u128;
+        let needed = mask(self.total_bits()) + 1;
+        let mut offset = 0u128;
+        let mut count = 1u128;
+        let mut length = 0;
+        loop {
+            length += 1;
+            count = match count.checked_mul(size) {
+                Some(count) => count,
+                None => return Ok((length, offset)),
+            };
+            if count >= needed {
+                return Ok((length, offset));
+            }
+            offset += count;
+        }
+    }
+}
+
+fn mask(bits: u32) -> u128 {
+    if bits == 0 {
+        0
+    } else {
+        u128::MAX >> (128 - bits)
+    }
+}
+
+struct IdState {
+    last_timestamp: Option<u64>,
+    last_tail: u128,
+    random: u64,
+}
+
+impl IdState {
+    /// Xorshift64*; fast and good enough to avoid collisions, but not secure.
+    fn next_random(&mut self) -> u64 {
+        self.random ^= self.random >> 12;
+        self.random ^= self.random << 25;
+        self.random ^= self.random >> 27;
+        self.random.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+/// Generator of unique ids that increase monotonically within the process (as long as the generator is shared).
+///
+/// If the clock goes backwards, the last timestamp is reused until the clock catches up.
+/// If the tail runs out within one millisecond, the timestamp is advanced by a millisecond.
+pub struct IdGenerator<C: Clock = SystemClock> {
+    layout: IdLayout,
+    node: u64,
+    tail: Tail,
+    clock: C,
+    state: Mutex<IdState>,
+}
+
+impl IdGenerator<SystemClock> {
+    /// Panics if the node does not fit in the layout.
+    pub fn new(layout: IdLayout, node: u64, tail: Tail) -> Self {
+        IdGenerator::with_clock(layout, node, tail, SystemClock)
+    }
+}
+
+impl<C: Clock> IdGenerator<C> {
+    /// Panics if the node does not fit in the layout.
+    pub fn with_clock(layout: IdLayout, node: u64, tail: Tail, clock: C) -> Self {
+        match IdGenerator::try_with_clock(layout, node, tail, clock) {
+            Some(generator) => generator,
+            None => panic!(
+                "node {} does not fit in the {} node bits of the id layout",
+                node, layout.node_bits
+            ),
+        }
+    }
+
+    /// Empty if the node does not fit in the layout.
+    pub fn try_with_clock(layout: IdLayout, node: u64, tail: Tail, clock: C) -> Option<Self> {
+        if node > layout.node_max() {
+            return None;
+        }
+        let mut seed = RandomState::new().build_hasher();
+        seed.write_u64(clock.now_millis());
+        seed.write_u64(node);
+        Some(IdGenerator {
+            layout,
+            node,
+            tail,
+            clock,
+            state: Mutex::new(IdState {
+                last_timestamp: None,
+                last_tail: 0,
+                random: seed.finish() | 1,
+            }),
+        })
+    }
+
+    pub fn layout(&self) -> &IdLayout {
+        &self.layout
+    }
+
+    /// Generate the next id. Always larger than the previous id from this generator.
+    /// Panics if the timestamp no longer fits in the layout.
+    pub fn next_id(&self) -> u128 {
+        match self.try_next_id() {
+            Ok(id) => id,
+            Err(_) => panic!(
+                "timestamp does not fit in the {} timestamp bits of the id layout",
+                self.layout.timestamp_bits
+            ),
+        }
+    }
+
+    /// Like `next_id`, but fails with `TooLarge` (with the largest id of the layout)
+    /// once the timestamp no longer fits, instead of panicking.
+    pub fn try_next_id(&self) -> Result<u128, N2NErr> {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner());
+        let now = self
+            .clock
+            .now_millis()
+            .saturating_sub(self.layout.epoch_millis);
+        let (timestamp, tail) = match state.last_timestamp {
+            Some(last) if now <= last => {
+                if state.last_tail < self.layout.tail_max() {
+                    (last, state.last_tail + 1)
+                } else {
+                    (last.saturating_add(1), self.first_tail(&mut state))
+                }
+            }
+            _ => (now, self.first_tail(&mut state)),
+        };
+        if timestamp > self.layout.timestamp_max() {
+            return Err(N2NErr::TooLarge {
+                charset: None,
+                max: mask(self.layout.total_bits()),
+            });
+        }
+        state.last_timestamp = Some(timestamp);
+        state.last_tail = tail;
+        Ok(
+            ((timestamp as u128) << (self.layout.node_bits + self.layout.tail_bits))
+                | ((self.node as u128) << self.layout.tail_bits)
+                | tail,
+        )
+    }
+
+    /// Generate the next id and encode it as a fixed-width name (see `IdLayout::encode`).
+    /// Panics like `next_id` and `IdLayout::encode`.
+    pub fn next_name(&self, charset: &Charset) -> String {
+        self.layout.encode(self.next_id(), charset)
+    }
+
+    /// Like `next_name`, but fails instead of panicking. No id is used up if the charset is rejected.
+    pub fn try_next_name(&self, charset: &Charset) -> Result<String, N2NErr> {
+        self.layout.fixed_width(charset)?;
+        self.layout.try_encode(self.try_next_id()?, charset)
+    }
+
+    fn first_tail(&self, state: &mut IdState) -> u128 {
+        match self.tail {
+            Tail::Sequence => 0,
+            Tail::Random => {
+                let high = state.next_random() as u128;
+                let low = state.next_random() as u128;
+                // Keep the top half of the tail free, so that many ids fit in the same millisecond.
+                ((high << 64) | low) & (self.layout.tail_max() >> 1)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::std::cell::Cell;
+
+    use super::*;
+
+    mod layout {
+        use super::*;
+
+        #[test]
+        fn invalid() {
+            assert!(IdLayout::try_new(0, 10, 10).is_none());
+            assert!(IdLayout::try_new(65, 10, 10).is_none());
+            assert!(IdLayout::try_new(40, 65, 10).is_none());
+            assert!(IdLayout::try_new(40, 10, 0).is_none());
+            assert!(IdLayout::try_new(64, 32, 31).is_none());
+            assert!(IdLayout::try_new(64, 32, 30).is_some());
+            assert!(IdLayout::new(64, 0, 1).try_with_epoch(1).is_none());
+            assert!(IdLayout::new(63, 0, 1).try_with_epoch(1 << 63).is_some());
+            assert!(IdLayout::new(63, 0, 1)
+                .try_with_epoch((1 << 63) + 1)
+                .is_none());
+        }
+
+        #[test]
+        fn split_max() {
+            let layout = IdLayout::new(63, 0, 1).with_epoch(1 << 63);
+            let parts = layout.split(u128::MAX);
+            assert_eq!(parts.timestamp, u64::MAX);
+            assert_eq!(parts.tail, 1);
+            let parts = IdLayout::new(64, 0, 1).split(u128::MAX);
+            assert_eq!(parts.timestamp, u64::MAX);
+        }
+
+        #[test]
+        fn compose_split() {
+            let layout = IdLayout::new(8, 4, 4).with_epoch(1000);
+            let parts = IdParts {
+                timestamp: 1003,
+                node: 5,
+                tail: 9,
+            };
+            let id = layout.compose(parts).unwrap();
+            assert_eq!(id, 0x0359);
+            assert_eq!(layout.split(id), parts);
+        }
+
+        #[test]
+        fn compose_too_large() {
+            let layout = IdLayout::new(8, 4, 4);
+            let parts = IdParts {
+                timestamp: 3,
+                node: 16,
+                tail: 0,
+            };
+            assert!(layout.compose(parts).is_none());
+        }
+
+        #[test]
+        fn fixed_width() {
+            let charset = Charset::case_sensitive("abc");
+            let layout = IdLayout::new(1, 0, 2);
+            let names: Vec<String> = (0..8).map(|id| layout.encode(id, &charset)).collect();
+            assert_eq!(names, vec!["aa", "ab", "ac", "ba", "bb", "bc", "ca", "cb"]);
+        }
+
+        #[test]
+        fn single_character() {
+            let charset = Charset::case_sensitive("a");
+            let layout = IdLayout::new(1, 0, 2);
+            match layout.try_encode(1, &charset).unwrap_err() {
+                N2NErr::SingleCharacter { .. } => {}
+                _ => panic!("wrong error"),
+            }
+            match layout.decode("aa", &charset).unwrap_err() {
+                N2NErr::SingleCharacter { .. } => {}
+                _ => panic!("wrong error"),
+            }
+        }
+
+        #[test]
+        fn roundtrip_large() -> Result<(), N2NErr> {
+            let charset = Charset::case_sensitive("0123456789");
+            let layout = IdLayout::new(64, 0, 62);
+            let parts = IdParts {
+                timestamp: u64::MAX,
+                node: 0,
+                tail: (1 << 62) - 1,
+            };
+            let name = layout.encode(layout.compose(parts).unwrap(), &charset);
+            assert_eq!(layout.decode(&name, &charset)?, parts);
+            Ok(())
+        }
+
+        #[test]
+        fn decode_wrong_length() {
+            let charset = Charset::case_sensitive("abc");
+            let layout = IdLayout::new(1, 0, 2);
+            match layout.decode("abc", &charset).unwrap_err() {
+                N2NErr::InvalidLength { expected, actual } => {
+                    assert_eq!(expected, 2);
+                    assert_eq!(actual, 3);
+                }
+                _ => panic!("wrong error"),
+            }
+        }
+
+        #[test]
+        fn decode_beyond_layout() {
+            let charset = Charset::case_sensitive("abc");
+            let layout = IdLayout::new(1, 0, 2);
+            match layout.decode("cc", &charset).unwrap_err() {
+                N2NErr::TooLarge { .. } => {}
+                _ => panic!("wrong error"),
+            }
+        }
+    }
+
+    mod generator {
+        use super::*;
+
+        #[test]
+        fn sequence() {
+            let now = Cell::new(5u64);
+            let generator =
+                IdGenerator::with_clock(IdLayout::new(8, 4, 4), 3, Tail::Sequence, || now.get());
+            assert_eq!(generator.next_id(), 0x0530);
+            assert_eq!(generator.next_id(), 0x0531);
+            now.set(7);
+            assert_eq!(generator.next_id(), 0x0730);
+        }
+
+        #[test]
+        fn clock_regression() {
+            let now = Cell::new(5u64);
+            let generator =
+                IdGenerator::with_clock(IdLayout::new(8, 4, 4), 3, Tail::Sequence, || now.get());
+            assert_eq!(generator.next_id(), 0x0530);
+            now.set(2);
+            assert_eq!(generator.next_id(), 0x0531);
+            now.set(6);
+            assert_eq!(generator.next_id(), 0x0630);
+        }
+
+        #[test]
+        fn tail_exhausted() {
+            let generator =
+                IdGenerator::with_clock(IdLayout::new(8, 0, 2), 0, Tail::Sequence, || 5u64);
+            let ids: Vec<u128> = (0..6).map(|_| generator.next_id()).collect();
+            assert_eq!(ids, vec![0x14, 0x15, 0x16, 0x17, 0x18, 0x19]);
+        }
+
+        #[test]
+        fn timestamp_overflow() {
+            let now = Cell::new(2u64);
+            let generator =
+                IdGenerator::with_clock(IdLayout::new(2, 0, 1), 0, Tail::Sequence, || now.get());
+            assert_eq!(generator.try_next_id().unwrap(), 0b100);
+            now.set(3);
+            assert_eq!(generator.try_next_id().unwrap(), 0b110);
+            assert_eq!(generator.try_next_id().unwrap(), 0b111);
+            // The tail is exhausted, and the next millisecond does not fit.
+            match generator.try_next_id().unwrap_err() {
+                N2NErr::TooLarge { max, .. } => assert_eq!(max, 0b111),
+                _ => panic!("wrong error"),
+            }
+            now.set(4);
+            assert!(generator.try_next_id().is_err());
+            assert!(generator
+                .try_next_name(&Charset::case_sensitive("ab"))
+                .is_err());
+        }
+
+        #[test]
+        fn single_character_name() {
+            let generator =
+                IdGenerator::with_clock(IdLayout::new(8, 0, 2), 0, Tail::Sequence, || 5u64);
+            assert!(generator
+                .try_next_name(&Charset::case_sensitive("a"))
+                .is_err());
+            assert_eq!(generator.next_id(), 0x14);
+        }
+
+        #[test]
+        #[should_panic]
+        fn timestamp_overflow_panics() {
+            let generator =
+                IdGenerator::with_clock(IdLayout::new(2, 0, 1), 0, Tail::Sequence, || 4u64);
+            generator.next_id();
+        }
+
+        #[test]
+        fn random_monotonic() {
+            let generator =
+                IdGenerator::with_clock(IdLayout::ulid_like(), 0, Tail::Random, || 1234u64);
+            let mut previous = generator.next_id();
+            for _ in 0..1000 {
+                let id = generator.next_id();
+                assert!(id > previous);
+                assert_eq!(generator.layout().split(id).timestamp, 1234);
+                previous = id;
+            }
+        }
+
+        #[test]
+        fn invalid_node() {
+            assert!(IdGenerator::try_with_clock(
+                IdLayout::new(8, 4, 4),
+                16,
+                Tail::Sequence,
+                || 0u64
+            )
+            .is_none());
+        }
+
+        #[test]
+        fn names_sort() -> Result<(), N2NErr> {
+            let charset = Charset::case_insensitive("0123456789ABCDEFGHJKMNPQRSTVWXYZ");
+            let now = Cell::new(1_600_000_000_000u64);
+            let generator =
+                IdGenerator::with_clock(IdLayout::snowflake(), 7, Tail::Sequence, || now.get());
+            let mut names = Vec::new();
+            for step in 0..50 {
+                now.set(now.get() + step % 3);
+                names.push(generator.next_name(&charset));
+            }
+            let mut sorted = names.clone();
+            sorted.sort();
+            assert_eq!(names, sorted);
+            let parts = generator.layout().decode(&names[0], &charset)?;
+            assert_eq!(parts.timestamp, 1_600_000_000_000);
+            assert_eq!(parts.node, 7);
+            assert_eq!(parts.tail, 0);
+            Ok(())
+        }
+    }
+}
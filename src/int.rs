@@ -0,0 +1,353 @@
+use ::core::fmt;
+use ::core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+
+use crate::capacity::IntWidth;
+use crate::decode::{
+    name2number_i128, name2number_i16, name2number_i32, name2number_i64, name2number_u128,
+    name2number_u16, name2number_u32, name2number_u64,
+};
+use crate::encode::{
+    number2name_into_i128, number2name_into_i16, number2name_into_i32, number2name_into_i64,
+    number2name_into_u128, number2name_into_u16, number2name_into_u32, number2name_into_u64,
+};
+use crate::signs::{
+    signed2unsigned_128, signed2unsigned_16, signed2unsigned_32, signed2unsigned_64,
+    unsigned2signed_128, unsigned2signed_16, unsigned2signed_32, unsigned2signed_64,
+};
+use crate::typ::N2NErr;
+use crate::Charset;
+
+/// Integer types that can be converted to and from names, so code can be generic over them.
+///
+/// Signed numbers are mapped to unsigned ones first (see `signed2unsigned`), and non-zero numbers
+/// are shifted down by one, so that no names are wasted.
+/// The largest number that is encoded for the type is `IntWidth::ENCODED_MAX`.
+pub trait N2NInt: IntWidth + Copy {
+    /// Write the name of the number, without allocating.
+    fn encode_into(self, charset: &Charset, out: &mut impl fmt::Write) -> fmt::Result;
+
+    /// Convert a name back to a number of this type.
+    fn decode(text: &str, charset: &Charset) -> Result<Self, N2NErr>;
+
+    /// The number of this type that is encoded as `number`, if it is at most `ENCODED_MAX`.
+    fn from_encoded(number: u128) -> Option<Self>;
+
+    /// The number that is encoded for this one, the inverse of `from_encoded`.
+    fn to_encoded(self) -> u128;
+}
+
+macro_rules! n2n_int_for_type {
+    ($int: ty, $unsigned: ty, $encode_into: ident, $decode: ident, $to_unsigned: expr, $from_unsigned: expr) => {
+        impl N2NInt for $int {
+            fn encode_into(self, charset: &Charset, out: &mut impl fmt::Write) -> fmt::Result {
+                $encode_into(self, charset, out)
+            }
+
+            fn decode(text: &str, charset: &Charset) -> Result<Self, N2NErr> {
+                $decode(text, charset)
+            }
+
+            fn from_encoded(number: u128) -> Option<Self> {
+                let from_unsigned: fn($unsigned) -> $int = $from_unsigned;
+                <$unsigned>::try_from(number).ok().map(from_unsigned)
+            }
+
+            fn to_encoded(self) -> u128 {
+                let to_unsigned: fn($int) -> $unsigned = $to_unsigned;
+                to_unsigned(self) as u128
+            }
+        }
+    };
+}
+
+n2n_int_for_type!(
+    u16,
+    u16,
+    number2name_into_u16,
+    name2number_u16,
+    |nr| nr,
+    |nr| nr
+);
+n2n_int_for_type!(
+    u32,
+    u32,
+    number2name_into_u32,
+    name2number_u32,
+    |nr| nr,
+    |nr| nr
+);
+n2n_int_for_type!(
+    u64,
+    u64,
+    number2name_into_u64,
+    name2number_u64,
+    |nr| nr,
+    |nr| nr
+);
+n2n_int_for_type!(
+    u128,
+    u128,
+    number2name_into_u128,
+    name2number_u128,
+    |nr| nr,
+    |nr| nr
+);
+n2n_int_for_type!(
+    i16,
+    u16,
+    number2name_into_i16,
+    name2number_i16,
+    signed2unsigned_16,
+    unsigned2signed_16
+);
+n2n_int_for_type!(
+    i32,
+    u32,
+    number2name_into_i32,
+    name2number_i32,
+    signed2unsigned_32,
+    unsigned2signed_32
+);
+n2n_int_for_type!(
+    i64,
+    u64,
+    number2name_into_i64,
+    name2number_i64,
+    signed2unsigned_64,
+    unsigned2signed_64
+);
+n2n_int_for_type!(
+    i128,
+    u128,
+    number2name_into_i128,
+    name2number_i128,
+    signed2unsigned_128,
+    unsigned2signed_128
+);
+
+/// Types without their own functions use those of a wider type, which gives the same names.
+macro_rules! n2n_int_via_wider_type {
+    ($int: ty, $wider: ty) => {
+        impl N2NInt for $int {
+            fn encode_into(self, charset: &Charset, out: &mut impl fmt::Write) -> fmt::Result {
+                (self as $wider).encode_into(charset, out)
+            }
+
+            fn decode(text: &str, charset: &Charset) -> Result<Self, N2NErr> {
+                let number = <$wider>::decode(text, charset)
+                    .map_err(|err| err.with_max(Self::ENCODED_MAX))?;
+                <$int>::try_from(number).map_err(|_| N2NErr::TooLarge {
+                    charset: Some(charset.clone()),
+                    max: Self::ENCODED_MAX,
+                })
+            }
+
+            fn from_encoded(number: u128) -> Option<Self> {
+                <$int>::try_from(<$wider>::from_encoded(number)?).ok()
+            }
+
+            fn to_encoded(self) -> u128 {
+                (self as $wider).to_encoded()
+            }
+        }
+    };
+}
+
+n2n_int_via_wider_type!(u8, u16);
+n2n_int_via_wider_type!(i8, i16);
+n2n_int_via_wider_type!(usize, u64);
+n2n_int_via_wider_type!(isize, i64);
+
+macro_rules! n2n_int_for_nonzero_type {
+    ($nonzero: ty, $int: ty, $unsigned: ty, $to_unsigned: expr, $from_unsigned: expr) => {
+        impl IntWidth for $nonzero {
+            const ENCODED_MAX: u128 = <$unsigned>::MAX as u128 - 1;
+        }
+
+        impl N2NInt for $nonzero {
+            fn encode_into(self, charset: &Charset, out: &mut impl fmt::Write) -> fmt::Result {
+                let to_unsigned: fn($int) -> $unsigned = $to_unsigned;
+                (to_unsigned(self.get()) - 1).encode_into(charset, out)
+            }
+
+            fn decode(text: &str, charset: &Charset) -> Result<Self, N2NErr> {
+                let from_unsigned: fn($unsigned) -> $int = $from_unsigned;
+                <$unsigned>::decode(text, charset)
+                    .map_err(|err| err.with_max(Self::ENCODED_MAX))?
+                    .checked_add(1)
+                    .and_then(|number| <$nonzero>::new(from_unsigned(number)))
+                    .ok_or_else(|| N2NErr::TooLarge {
+                        charset: Some(charset.clone()),
+                        max: Self::ENCODED_MAX,
+                    })
+            }
+
+            fn from_encoded(number: u128) -> Option<Self> {
+                let from_unsigned: fn($unsigned) -> $int = $from_unsigned;
+                <$unsigned>::try_from(number)
+                    .ok()?
+                    .checked_add(1)
+                    .and_then(|number| <$nonzero>::new(from_unsigned(number)))
+            }
+
+            fn to_encoded(self) -> u128 {
+                let to_unsigned: fn($int) -> $unsigned = $to_unsigned;
+                (to_unsigned(self.get()) - 1) as u128
+            }
+        }
+    };
+}
+
+n2n_int_for_nonzero_type!(NonZeroU8, u8, u8, |nr| nr, |nr| nr);
+n2n_int_for_nonzero_type!(NonZeroU16, u16, u16, |nr| nr, |nr| nr);
+n2n_int_for_nonzero_type!(NonZeroU32, u32, u32, |nr| nr, |nr| nr);
+n2n_int_for_nonzero_type!(NonZeroU64, u64, u64, |nr| nr, |nr| nr);
+n2n_int_for_nonzero_type!(NonZeroU128, u128, u128, |nr| nr, |nr| nr);
+n2n_int_for_nonzero_type!(NonZeroUsize, usize, usize, |nr| nr, |nr| nr);
+n2n_int_for_nonzero_type!(
+    NonZeroI8,
+    i8,
+    u8,
+    |nr| signed2unsigned_16(nr as i16) as u8,
+    |nr| unsigned2signed_16(nr as u16) as i8
+);
+n2n_int_for_nonzero_type!(NonZeroI16, i16, u16, signed2unsigned_16, unsigned2signed_16);
+n2n_int_for_nonzero_type!(NonZeroI32, i32, u32, signed2unsigned_32, unsigned2signed_32);
+n2n_int_for_nonzero_type!(NonZeroI64, i64, u64, signed2unsigned_64, unsigned2signed_64);
+n2n_int_for_nonzero_type!(
+    NonZeroI128,
+    i128,
+    u128,
+    signed2unsigned_128,
+    unsigned2signed_128
+);
+n2n_int_for_nonzero_type!(
+    NonZeroIsize,
+    isize,
+    usize,
+    |nr| signed2unsigned_64(nr as i64) as usize,
+    |nr| unsigned2signed_64(nr as u64) as isize
+);
+
+#[cfg(test)]
+mod tests {
+    use ::core::fmt::Debug;
+
+    use super::*;
+
+    fn roundtrip<T: N2NInt + PartialEq + Debug>(charset: &Charset, numbers: &[T]) {
+        for &number in numbers {
+            let name = charset.encode_int(number);
+            assert_eq!(charset.decode_int::<T>(&name).unwrap(), number);
+        }
+    }
+
+    #[test]
+    fn all_types() {
+        let charset = Charset::case_sensitive("abc");
+        roundtrip(&charset, &[0u8, 1, 200, u8::MAX]);
+        roundtrip(&charset, &[0u16, 1, u16::MAX]);
+        roundtrip(&charset, &[0u32, 1, u32::MAX]);
+        roundtrip(&charset, &[0u64, 1, u64::MAX]);
+        roundtrip(&charset, &[0u128, 1, u128::MAX]);
+        roundtrip(&charset, &[0usize, 1, usize::MAX]);
+        roundtrip(&charset, &[0i8, -1, i8::MIN, i8::MAX]);
+        roundtrip(&charset, &[0i16, -1, i16::MIN, i16::MAX]);
+        roundtrip(&charset, &[0i32, -1, i32::MIN, i32::MAX]);
+        roundtrip(&charset, &[0i64, -1, i64::MIN, i64::MAX]);
+        roundtrip(&charset, &[0i128, -1, i128::MIN, i128::MAX]);
+        roundtrip(&charset, &[0isize, -1, isize::MIN, isize::MAX]);
+        roundtrip(&charset, &[NonZeroU8::MIN, NonZeroU8::MAX]);
+        roundtrip(&charset, &[NonZeroU64::MIN, NonZeroU64::MAX]);
+        roundtrip(&charset, &[NonZeroUsize::MIN, NonZeroUsize::MAX]);
+        roundtrip(&charset, &[NonZeroI8::MIN, NonZeroI8::MAX]);
+        roundtrip(&charset, &[NonZeroI32::MIN, NonZeroI32::MAX]);
+        roundtrip(&charset, &[NonZeroI128::MIN, NonZeroI128::MAX]);
+        roundtrip(&charset, &[NonZeroIsize::MIN, NonZeroIsize::MAX]);
+    }
+
+    #[test]
+    fn same_names_for_all_widths() {
+        let charset = Charset::case_sensitive("abc");
+        assert_eq!(charset.encode_int(200u8), charset.encode_int(200u64));
+        assert_eq!(charset.encode_int(200usize), charset.encode_int(200u128));
+        assert_eq!(charset.encode_int(-100i8), charset.encode_int(-100i64));
+        assert_eq!(charset.encode_int(-100isize), charset.encode_int(-100i32));
+    }
+
+    #[test]
+    fn nonzero_uses_all_names() {
+        let charset = Charset::case_sensitive("abc");
+        assert_eq!(charset.encode_int(NonZeroU32::new(1).unwrap()), "a");
+        assert_eq!(charset.encode_int(NonZeroU32::new(4).unwrap()), "aa");
+        assert_eq!(charset.encode_int(NonZeroI32::new(-1).unwrap()), "a");
+        assert_eq!(charset.encode_int(NonZeroI32::new(1).unwrap()), "b");
+        assert_eq!(
+            charset.decode_int::<NonZeroU32>("a").unwrap(),
+            NonZeroU32::new(1).unwrap()
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        let charset = Charset::case_sensitive("abc");
+        for name in [charset.encode_int(256u16), charset.encode_int(u16::MAX)] {
+            match charset.decode_int::<u8>(&name).unwrap_err() {
+                N2NErr::TooLarge { .. } => {}
+                _ => panic!("wrong error"),
+            }
+        }
+        match charset
+            .decode_int::<i8>(&charset.encode_int(128i16))
+            .unwrap_err()
+        {
+            N2NErr::TooLarge { .. } => {}
+            _ => panic!("wrong error"),
+        }
+        match charset
+            .decode_int::<NonZeroU16>(&charset.encode_int(u16::MAX))
+            .unwrap_err()
+        {
+            N2NErr::TooLarge { .. } => {}
+            _ => panic!("wrong error"),
+        }
+    }
+
+    #[test]
+    fn from_encoded() {
+        assert_eq!(u8::from_encoded(255), Some(255));
+        assert_eq!(u8::from_encoded(256), None);
+        assert_eq!(i8::from_encoded(255), Some(i8::MIN));
+        assert_eq!(i8::from_encoded(256), None);
+        assert_eq!(i64::from_encoded(1), Some(-1));
+        assert_eq!(NonZeroU16::from_encoded(0), NonZeroU16::new(1));
+        assert_eq!(NonZeroU16::from_encoded(u16::MAX as u128), None);
+        assert_eq!(u128::from_encoded(u128::MAX), Some(u128::MAX));
+    }
+
+    #[test]
+    fn to_encoded() {
+        assert_eq!(255u8.to_encoded(), 255);
+        assert_eq!(i8::MIN.to_encoded(), 255);
+        assert_eq!((-1i64).to_encoded(), 1);
+        assert_eq!(NonZeroU16::MIN.to_encoded(), 0);
+        assert_eq!(NonZeroI32::new(1).unwrap().to_encoded(), 1);
+        for number in [0i32, -7, 7, i32::MIN, i32::MAX] {
+            assert_eq!(i32::from_encoded(number.to_encoded()), Some(number));
+        }
+    }
+
+    #[test]
+    fn encoded_max() {
+        assert_eq!(<i32 as IntWidth>::ENCODED_MAX, u32::MAX as u128);
+        assert_eq!(<NonZeroU8 as IntWidth>::ENCODED_MAX, 254);
+        let charset = Charset::case_sensitive("ab");
+        assert_eq!(
+            charset.encode_int(NonZeroU8::MAX).len(),
+            charset.max_len_for::<NonZeroU8>()
+        );
+    }
+}
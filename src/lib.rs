@@ -1,3 +1,47 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(test, allow(clippy::legacy_numeric_constants))]
+
+// Not behind a feature: a `Charset` keeps its characters in an `Arc`, and decoding errors hold the
+// charset and a `Vec` of invalid characters, so there is nothing useful left without allocation.
+extern crate alloc;
+// So that code generated by the derive macro also works inside this crate.
+#[cfg(feature = "derive")]
+extern crate self as number2name;
+
+#[cfg(feature = "std")]
+pub use crate::allocator::NameAllocator;
+pub use crate::arith::name_add;
+pub use crate::arith::name_cmp;
+pub use crate::arith::name_distance;
+pub use crate::arith::name_pred;
+pub use crate::arith::name_sub;
+pub use crate::arith::name_succ;
+pub use crate::batch::decode_batch;
+pub use crate::batch::encode_batch;
+#[cfg(feature = "builtin-charsets")]
+pub use crate::builtin::builtin_charset;
+#[cfg(feature = "builtin-charsets")]
+pub use crate::builtin::builtin_charsets;
+#[cfg(feature = "builtin-charsets")]
+pub use crate::builtin::Base32;
+#[cfg(feature = "builtin-charsets")]
+pub use crate::builtin::Base32Crockford;
+#[cfg(feature = "builtin-charsets")]
+pub use crate::builtin::Base32Hex;
+#[cfg(feature = "builtin-charsets")]
+pub use crate::builtin::Base32Human;
+#[cfg(feature = "builtin-charsets")]
+pub use crate::builtin::Base32Lowercase;
+#[cfg(feature = "builtin-charsets")]
+pub use crate::builtin::Base32Scny;
+#[cfg(feature = "builtin-charsets")]
+pub use crate::builtin::Base64;
+#[cfg(feature = "builtin-charsets")]
+pub use crate::builtin::Base64Url;
+#[cfg(feature = "builtin-charsets")]
+pub use crate::builtin::Hex;
+#[cfg(feature = "builtin-charsets")]
+pub use crate::builtin::HexLowercase;
 #[cfg(feature = "builtin-charsets")]
 pub use crate::builtin::BASE32;
 #[cfg(feature = "builtin-charsets")]
@@ -18,25 +62,89 @@ pub use crate::builtin::BASE64URL;
 pub use crate::builtin::HEX;
 #[cfg(feature = "builtin-charsets")]
 pub use crate::builtin::HEXLOWERCASE;
+pub use crate::capacity::IntWidth;
+pub use crate::charset::Case;
 pub use crate::charset::Charset;
 pub use crate::decode::name2number;
 pub use crate::decode::name2number_i128;
 pub use crate::decode::name2number_i16;
 pub use crate::decode::name2number_i32;
 pub use crate::decode::name2number_i64;
+pub use crate::decode::name2number_prefix;
 pub use crate::decode::name2number_u128;
 pub use crate::decode::name2number_u16;
 pub use crate::decode::name2number_u32;
 pub use crate::decode::name2number_u64;
+pub use crate::decoder::Decoder;
 pub use crate::encode::number2name;
 pub use crate::encode::number2name_i128;
 pub use crate::encode::number2name_i16;
 pub use crate::encode::number2name_i32;
 pub use crate::encode::number2name_i64;
+pub use crate::encode::number2name_into_i128;
+pub use crate::encode::number2name_into_i16;
+pub use crate::encode::number2name_into_i32;
+pub use crate::encode::number2name_into_i64;
+pub use crate::encode::number2name_into_u128;
+pub use crate::encode::number2name_into_u16;
+pub use crate::encode::number2name_into_u32;
+pub use crate::encode::number2name_into_u64;
+pub use crate::encode::number2name_to_slice_i128;
+pub use crate::encode::number2name_to_slice_i16;
+pub use crate::encode::number2name_to_slice_i32;
+pub use crate::encode::number2name_to_slice_i64;
+pub use crate::encode::number2name_to_slice_u128;
+pub use crate::encode::number2name_to_slice_u16;
+pub use crate::encode::number2name_to_slice_u32;
+pub use crate::encode::number2name_to_slice_u64;
 pub use crate::encode::number2name_u128;
 pub use crate::encode::number2name_u16;
 pub use crate::encode::number2name_u32;
 pub use crate::encode::number2name_u64;
+pub use crate::encode::NameDisplay;
+pub use crate::group::GroupFrom;
+pub use crate::group::Grouping;
+pub use crate::id::Id;
+pub use crate::id::IdKind;
+#[cfg(feature = "std")]
+pub use crate::idgen::Clock;
+#[cfg(feature = "std")]
+pub use crate::idgen::IdGenerator;
+#[cfg(feature = "std")]
+pub use crate::idgen::IdLayout;
+#[cfg(feature = "std")]
+pub use crate::idgen::IdParts;
+#[cfg(feature = "std")]
+pub use crate::idgen::SystemClock;
+#[cfg(feature = "std")]
+pub use crate::idgen::Tail;
+pub use crate::int::N2NInt;
+pub use crate::names::Names;
+#[cfg(feature = "rayon")]
+pub use crate::parallel::par_decode;
+#[cfg(feature = "rayon")]
+pub use crate::parallel::par_decode_iter;
+#[cfg(feature = "rayon")]
+pub use crate::parallel::par_encode;
+#[cfg(feature = "rayon")]
+pub use crate::parallel::par_encode_iter;
+#[cfg(feature = "nom")]
+pub use crate::parse::nom_name;
+pub use crate::pronounce::Pronounceable;
+pub use crate::proquint::number2proquint;
+pub use crate::proquint::number2proquint_u128;
+pub use crate::proquint::number2proquint_u16;
+pub use crate::proquint::number2proquint_u32;
+pub use crate::proquint::number2proquint_u64;
+pub use crate::proquint::proquint2number;
+pub use crate::proquint::proquint2number_u128;
+pub use crate::proquint::proquint2number_u16;
+pub use crate::proquint::proquint2number_u32;
+pub use crate::proquint::proquint2number_u64;
+#[cfg(feature = "random")]
+pub use crate::random::random_number;
+pub use crate::scan::Found;
+pub use crate::scan::Scanner;
 pub use crate::signs::signed2unsigned;
 pub use crate::signs::signed2unsigned_128;
 pub use crate::signs::signed2unsigned_16;
@@ -47,14 +155,50 @@ pub use crate::signs::unsigned2signed_128;
 pub use crate::signs::unsigned2signed_16;
 pub use crate::signs::unsigned2signed_32;
 pub use crate::signs::unsigned2signed_64;
+pub use crate::static_charset::Name;
+pub use crate::static_charset::StaticCharset;
+pub use crate::typ::InvalidChar;
 pub use crate::typ::N2NErr;
+#[cfg(feature = "derive")]
+pub use ::number2name_derive::NameEncoded;
 
+#[cfg(feature = "std")]
+mod allocator;
+mod arith;
+mod batch;
 #[cfg(feature = "builtin-charsets")]
 mod builtin;
+mod capacity;
 mod charset;
 mod decode;
+mod decoder;
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::derive::*;
+}
+#[cfg(feature = "derive")]
+mod derive;
+mod digits;
+mod divide;
 mod encode;
+mod group;
+mod id;
+#[cfg(feature = "std")]
+mod idgen;
+mod int;
+mod names;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "nom")]
+mod parse;
+mod pronounce;
+mod proquint;
+#[cfg(feature = "random")]
+mod random;
+mod scan;
 mod signs;
+mod static_charset;
 mod typ;
 mod util;
 
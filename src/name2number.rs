@@ -1,12 +1,13 @@
 use ::std::io::stderr;
 use ::std::io::Write;
+use ::std::path::PathBuf;
 use ::std::process::exit;
 
 use ::structopt::StructOpt;
 
-use ::number2name::{name2number_i128, name2number_u128};
+use ::number2name::{name2number_u128, proquint2number, unsigned2signed_128, Pronounceable};
 
-use crate::cli_util::charset_by_identifier;
+use crate::cli_util::{charset_by_identifier, convert_lines, grouping_by_args, Scheme, SCHEMES};
 
 mod cli_util;
 
@@ -19,12 +20,20 @@ mod cli_util;
 pub struct Name2NrArgs {
     #[structopt(
         name = "NAMES",
-        required = true,
+        required_unless = "input",
         min_values = 1,
         help = "One or more encoded names"
     )]
     names: Vec<String>,
 
+    #[structopt(
+        short = "i",
+        long,
+        conflicts_with = "NAMES",
+        help = "Decode the name on each line of this file ('-' for standard input), using all cores with the rayon feature"
+    )]
+    input: Option<PathBuf>,
+
     #[structopt(
         short = "c",
         long,
@@ -33,12 +42,46 @@ pub struct Name2NrArgs {
     )]
     charset: String,
 
+    #[structopt(
+        long,
+        default_value = "charset",
+        possible_values = SCHEMES,
+        help = "Decode names from the charset, proquints or pronounceable names (as used when encoding)"
+    )]
+    scheme: Scheme,
+
     #[structopt(
         short = "s",
         long,
         help = "Use signed decoding instead of unsigned (has to match the option used when encoding)"
     )]
     signed: bool,
+
+    #[structopt(
+        long,
+        help = "Size of the groups that names are split into (separators are removed before decoding)"
+    )]
+    group: Option<usize>,
+
+    #[structopt(
+        long,
+        default_value = "-",
+        help = "Separator between groups (with --group)"
+    )]
+    separator: char,
+
+    #[structopt(
+        long,
+        help = "Groups are counted from the end of names instead of the start (with --group)"
+    )]
+    from_right: bool,
+
+    #[structopt(
+        long,
+        requires = "group",
+        help = "Reject names with separators in the wrong places, instead of ignoring them"
+    )]
+    strict: bool,
 }
 
 pub fn main() {
@@ -52,15 +95,48 @@ pub fn main() {
 
 fn go(args: &Name2NrArgs) -> Result<(), String> {
     let charset = charset_by_identifier(&args.charset)?;
-
-    for name in &args.names {
-        if args.signed {
-            let nr = name2number_i128(name, &charset).map_err(|err| err.as_string())?;
-            println!("{}", nr);
+    let grouping = grouping_by_args(
+        args.group,
+        args.separator,
+        args.from_right,
+        &charset,
+        args.scheme,
+    )?
+    .map(|grouping| {
+        if args.strict {
+            grouping.strict()
         } else {
-            let nr = name2number_u128(name, &charset).map_err(|err| err.as_string())?;
-            println!("{}", nr);
+            grouping
+        }
+    });
+    let pronounceable = Pronounceable::default();
+
+    let decode = |name: &str| {
+        let name = match &grouping {
+            Some(grouping) => grouping
+                .strip(name, &charset)
+                .map_err(|err| err.as_string())?,
+            None => name.to_owned(),
+        };
+        let nr = match args.scheme {
+            Scheme::Charset => name2number_u128(&name, &charset),
+            Scheme::Proquint => proquint2number(&name),
+            Scheme::Pronounceable => pronounceable.decode(&name),
         }
+        .map_err(|err| err.as_string())?;
+        Ok(if args.signed {
+            unsigned2signed_128(nr).to_string()
+        } else {
+            nr.to_string()
+        })
+    };
+
+    if let Some(input) = &args.input {
+        return convert_lines(input, decode);
+    }
+
+    for name in &args.names {
+        println!("{}", decode(name)?);
     }
 
     Ok(())
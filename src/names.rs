@@ -0,0 +1,277 @@
+use ::alloc::string::String;
+use ::alloc::vec;
+use ::alloc::vec::Vec;
+use ::core::cmp::Ordering;
+use ::core::ops::{Bound, RangeBounds};
+
+use crate::digits;
+use crate::Charset;
+
+/// Iterator over consecutive names, in the order of the numbers they represent.
+///
+/// Each name is found by incrementing the previous one, instead of encoding each number.
+/// Skipping ahead (`nth`) takes time proportional to the name length, not to the number of skipped names.
+#[derive(Debug, Clone)]
+pub struct Names<'a> {
+    charset: &'a Charset,
+    front: Vec<usize>,
+    back: Vec<usize>,
+    /// Number of names left, unless that does not fit in a u128.
+    remaining: Option<u128>,
+    done: bool,
+}
+
+impl<'a> Names<'a> {
+    /// All names for numbers in the range.
+    /// Empty for a single-character charset if the last name does not fit in memory (e.g. for an unbounded range),
+    /// since it has a character for every number.
+    pub fn new(charset: &'a Charset, range: impl RangeBounds<u128>) -> Option<Self> {
+        let first = match range.start_bound() {
+            Bound::Included(&start) => Some(start),
+            Bound::Excluded(&start) => start.checked_add(1),
+            Bound::Unbounded => Some(0),
+        };
+        let last = match range.end_bound() {
+            Bound::Included(&end) => Some(end),
+            Bound::Excluded(&end) => end.checked_sub(1),
+            Bound::Unbounded => Some(u128::MAX),
+        };
+        match (first, last) {
+            (Some(first), Some(last)) if first <= last => Some(Names {
+                charset,
+                front: digits::from_u128(first, charset.len())?,
+                back: digits::from_u128(last, charset.len())?,
+                remaining: (last - first).checked_add(1),
+                done: false,
+            }),
+            _ => Some(Names::empty(charset)),
+        }
+    }
+
+    /// All names that have exactly the given number of characters.
+    /// These do not need to fit in a u128.
+    pub fn of_length(charset: &'a Charset, length: usize) -> Self {
+        if length == 0 {
+            return Names::empty(charset);
+        }
+        Names {
+            charset,
+            front: vec![0; length],
+            back: vec![charset.len() - 1; length],
+            remaining: u32::try_from(length)
+                .ok()
+                .and_then(|length| (charset.len() as u128).checked_pow(length)),
+            done: false,
+        }
+    }
+
+    fn empty(charset: &'a Charset) -> Self {
+        Names {
+            charset,
+            front: Vec::new(),
+            back: Vec::new(),
+            remaining: Some(0),
+            done: true,
+        }
+    }
+
+    fn render(&self, digits: &[usize]) -> String {
+        digits.iter().map(|&index| self.charset[index]).collect()
+    }
+
+    fn consume_one(&mut self) {
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+        }
+        if self.front == self.back {
+            self.done = true;
+        }
+    }
+}
+
+impl Iterator for Names<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let name = self.render(&self.front);
+        self.consume_one();
+        if !self.done {
+            digits::increment(&mut self.front, self.charset.len());
+        }
+        Some(name)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        match self.remaining.map(usize::try_from) {
+            Some(Ok(remaining)) => (remaining, Some(remaining)),
+            _ => (usize::MAX, None),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if let Some(remaining) = self.remaining {
+            if n as u128 >= remaining {
+                self.remaining = Some(0);
+                self.done = true;
+                return None;
+            }
+            self.remaining = Some(remaining - n as u128);
+        }
+        if !digits::add(&mut self.front, n as u128, self.charset.len())
+            || digits::cmp(&self.front, &self.back) == Ordering::Greater
+        {
+            self.done = true;
+            return None;
+        }
+        self.next()
+    }
+}
+
+impl DoubleEndedIterator for Names<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let name = self.render(&self.back);
+        self.consume_one();
+        if !self.done {
+            digits::decrement(&mut self.back, self.charset.len());
+        }
+        Some(name)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if let Some(remaining) = self.remaining {
+            if n as u128 >= remaining {
+                self.remaining = Some(0);
+                self.done = true;
+                return None;
+            }
+            self.remaining = Some(remaining - n as u128);
+        }
+        if !digits::sub(&mut self.back, n as u128, self.charset.len())
+            || digits::cmp(&self.front, &self.back) == Ordering::Greater
+        {
+            self.done = true;
+            return None;
+        }
+        self.next_back()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expected(charset: &Charset, numbers: impl Iterator<Item = u128>) -> Vec<String> {
+        numbers.map(|number| charset.encode_u128(number)).collect()
+    }
+
+    #[test]
+    fn forward() {
+        let charset = Charset::case_sensitive("abc");
+        let names: Vec<String> = charset.names(0..50).unwrap().collect();
+        assert_eq!(names, expected(&charset, 0..50));
+    }
+
+    #[test]
+    fn backward() {
+        let charset = Charset::case_sensitive("abc");
+        let names: Vec<String> = charset.names(7..=45).unwrap().rev().collect();
+        assert_eq!(names, expected(&charset, (7..=45).rev()));
+    }
+
+    #[test]
+    fn both_ends() {
+        let charset = Charset::case_sensitive("abc");
+        let mut names = charset.names(3..7).unwrap();
+        assert_eq!(names.size_hint(), (4, Some(4)));
+        assert_eq!(names.next().unwrap(), "aa");
+        assert_eq!(names.next_back().unwrap(), "ba");
+        assert_eq!(names.next_back().unwrap(), "ac");
+        assert_eq!(names.next().unwrap(), "ab");
+        assert!(names.next().is_none());
+        assert!(names.next_back().is_none());
+    }
+
+    #[test]
+    fn empty() {
+        let charset = Charset::case_sensitive("abc");
+        assert_eq!(charset.names(5..5).unwrap().count(), 0);
+        assert_eq!(charset.names(..0).unwrap().count(), 0);
+        assert_eq!(charset.names_of_length(0).count(), 0);
+    }
+
+    #[test]
+    fn skip() {
+        let charset = Charset::case_sensitive("abc");
+        let names: Vec<String> = charset.names(2..60).unwrap().skip(10).step_by(7).collect();
+        assert_eq!(names, expected(&charset, (12..60).step_by(7)));
+        let mut names = charset.names(2..60).unwrap();
+        assert_eq!(names.nth(57).unwrap(), charset.encode_u128(59));
+        assert!(names.next().is_none());
+        let mut names = charset.names(2..60).unwrap();
+        assert!(names.nth(58).is_none());
+        assert!(names.next_back().is_none());
+    }
+
+    #[test]
+    fn skip_back() {
+        let charset = Charset::case_sensitive("abc");
+        let mut names = charset.names(2..60).unwrap();
+        assert_eq!(names.nth_back(30).unwrap(), charset.encode_u128(29));
+        assert_eq!(names.nth(26).unwrap(), charset.encode_u128(28));
+        assert!(names.next_back().is_none());
+    }
+
+    #[test]
+    fn u128_limits() {
+        let charset = Charset::case_sensitive("abc");
+        let mut names = charset.names(u128::MAX - 2..).unwrap();
+        assert_eq!(names.size_hint(), (3, Some(3)));
+        assert_eq!(names.next_back().unwrap(), charset.encode_u128(u128::MAX));
+        assert_eq!(names.count(), 2);
+        let mut names = charset.names(..).unwrap();
+        assert_eq!(names.size_hint(), (usize::MAX, None));
+        assert_eq!(
+            names.nth(u64::MAX as usize).unwrap(),
+            charset.encode_u128(u64::MAX as u128)
+        );
+    }
+
+    #[test]
+    fn single_character() {
+        let charset = Charset::case_sensitive("x");
+        let names: Vec<String> = charset.names(1..4).unwrap().collect();
+        assert_eq!(names, ["xx", "xxx", "xxxx"]);
+        let mut names = charset.names(10..1 << 20).unwrap();
+        assert_eq!(names.nth(1 << 19).unwrap().len(), (1 << 19) + 11);
+        assert_eq!(names.next_back().unwrap().len(), 1 << 20);
+        assert!(charset.names(..).is_none());
+        assert!(charset.names(5..=u64::MAX as u128).is_none());
+    }
+
+    #[test]
+    fn of_length() {
+        let charset = Charset::case_sensitive("abc");
+        let names: Vec<String> = charset.names_of_length(2).collect();
+        assert_eq!(names, expected(&charset, 3..12));
+        let mut names = charset.names_of_length(100);
+        assert_eq!(names.size_hint(), (usize::MAX, None));
+        assert_eq!(names.next().unwrap(), "a".repeat(100));
+        assert_eq!(names.next_back().unwrap(), "c".repeat(100));
+        assert_eq!(names.nth_back(2).unwrap(), format!("{}bc", "c".repeat(98)));
+    }
+}
@@ -1,12 +1,21 @@
-use ::std::io::stderr;
 use ::std::io::Write;
+use ::std::io::{stderr, stdout};
+use ::std::ops::Bound;
+use ::std::path::PathBuf;
 use ::std::process::exit;
+use ::std::str::FromStr;
 
+use ::structopt::clap::AppSettings;
 use ::structopt::StructOpt;
 
-use ::number2name::{number2name_i128, number2name_u128};
+#[cfg(feature = "random")]
+use ::number2name::random_number;
+use ::number2name::{
+    number2name_u128, number2proquint, signed2unsigned_128, Charset, N2NInt, NameAllocator,
+    Pronounceable,
+};
 
-use crate::cli_util::charset_by_identifier;
+use crate::cli_util::{charset_by_identifier, convert_lines, grouping_by_args, Scheme, SCHEMES};
 
 mod cli_util;
 
@@ -14,12 +23,13 @@ mod cli_util;
 #[structopt(
     name = "number2name",
     author = "github.com/mverleg/number2name",
-    about = "Encode a string as a short number."
+    about = "Encode a string as a short number.",
+    setting = AppSettings::SubcommandsNegateReqs
 )]
 pub struct Nr2NameArgs {
     #[structopt(
         name = "NUMBERS",
-        required = true,
+        required_unless_one = &["range", "random", "input"],
         min_values = 1,
         help = "One or more integer numbers (without thousand separators)"
     )]
@@ -33,12 +43,97 @@ pub struct Nr2NameArgs {
     )]
     charset: String,
 
+    #[structopt(
+        long,
+        default_value = "charset",
+        possible_values = SCHEMES,
+        help = "Use the charset, proquints (e.g. 'lusab-babad') or pronounceable names (e.g. 'bakoda')"
+    )]
+    scheme: Scheme,
+
     #[structopt(
         short = "s",
         long,
         help = "Use signed encoding instead of unsigned (supporting negative numbers)"
     )]
     signed: bool,
+
+    #[structopt(
+        short = "r",
+        long,
+        conflicts_with = "NUMBERS",
+        help = "Encode all the numbers in a range, like '10..20' or '10..=20'"
+    )]
+    range: Option<String>,
+
+    #[structopt(
+        short = "i",
+        long,
+        conflicts_with_all = &["NUMBERS", "range", "random"],
+        help = "Encode the number on each line of this file ('-' for standard input), using all cores with the rayon feature"
+    )]
+    input: Option<PathBuf>,
+
+    #[structopt(
+        long,
+        conflicts_with = "NUMBERS",
+        help = "Print a secure random name; from --range if given, otherwise with --bits of entropy (needs the random feature)"
+    )]
+    random: bool,
+
+    #[structopt(
+        long,
+        default_value = "128",
+        help = "Minimum entropy of random names (without --range)"
+    )]
+    #[cfg_attr(not(feature = "random"), allow(dead_code))]
+    bits: u32,
+
+    #[structopt(
+        long,
+        help = "Make names easier to read by inserting a separator every this many characters"
+    )]
+    group: Option<usize>,
+
+    #[structopt(
+        long,
+        default_value = "-",
+        help = "Separator to insert between groups (with --group)"
+    )]
+    separator: char,
+
+    #[structopt(
+        long,
+        help = "Count groups from the end of names instead of the start (with --group)"
+    )]
+    from_right: bool,
+
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Command {
+    #[structopt(about = "Print the next name(s) from a counter stored in a file")]
+    Next {
+        #[structopt(
+            short = "f",
+            long,
+            help = "File that stores the next free number (created if it does not exist)"
+        )]
+        file: PathBuf,
+
+        #[structopt(
+            short = "n",
+            long,
+            default_value = "1",
+            help = "How many names to reserve"
+        )]
+        count: u64,
+    },
+
+    #[structopt(about = "Print how long names get for the character set")]
+    Inspect,
 }
 
 pub fn main() {
@@ -52,8 +147,137 @@ pub fn main() {
 
 fn go(args: &Nr2NameArgs) -> Result<(), String> {
     let charset = charset_by_identifier(&args.charset)?;
+    let grouping = grouping_by_args(
+        args.group,
+        args.separator,
+        args.from_right,
+        &charset,
+        args.scheme,
+    )?;
+    let show = |name: String| match &grouping {
+        Some(grouping) => grouping.format(name),
+        None => name,
+    };
+    let pronounceable = Pronounceable::default();
+    let encode = |nr: u128| {
+        show(match args.scheme {
+            Scheme::Charset => number2name_u128(nr, &charset),
+            Scheme::Proquint => number2proquint(nr),
+            Scheme::Pronounceable => pronounceable.encode(nr),
+        })
+    };
 
-    for nr_txt in &args.names {
+    if let Some(Command::Next { file, count }) = &args.command {
+        let allocator = NameAllocator::new(file, charset.clone());
+        let numbers = allocator.reserve(*count).map_err(|err| {
+            format!(
+                "Could not reserve names using '{}': {}",
+                file.display(),
+                err
+            )
+        })?;
+        for nr in numbers {
+            println!("{}", encode(nr as u128));
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Inspect) = &args.command {
+        inspect(&charset);
+        return Ok(());
+    }
+
+    #[cfg(not(feature = "random"))]
+    if args.random {
+        return Err(
+            "Random names need number2name to be built with the 'random' feature".to_owned(),
+        );
+    }
+
+    #[cfg(feature = "random")]
+    if args.random {
+        let random_err = |err: ::std::io::Error| format!("Could not generate random name: {}", err);
+        let name = match &args.range {
+            Some(range_txt) if args.signed => {
+                // Flipping the sign bit maps signed numbers to unsigned ones in the same order.
+                let (start, end, inclusive) = parse_range::<i128>(range_txt)?;
+                let flip = |nr: i128| (nr as u128) ^ (1 << 127);
+                let end = if inclusive {
+                    Bound::Included(flip(end))
+                } else {
+                    Bound::Excluded(flip(end))
+                };
+                let nr = random_number((Bound::Included(flip(start)), end)).map_err(random_err)?;
+                encode(signed2unsigned_128((nr ^ (1 << 127)) as i128))
+            }
+            Some(range_txt) => {
+                let (start, end, inclusive) = parse_range::<u128>(range_txt)?;
+                let end = if inclusive {
+                    Bound::Included(end)
+                } else {
+                    Bound::Excluded(end)
+                };
+                encode(random_number((Bound::Included(start), end)).map_err(random_err)?)
+            }
+            None if args.scheme == Scheme::Charset => {
+                show(charset.random_token(args.bits).map_err(random_err)?)
+            }
+            None => {
+                let nr = match 1u128.checked_shl(args.bits) {
+                    Some(end) => random_number(0..end),
+                    None => random_number(..),
+                };
+                encode(nr.map_err(random_err)?)
+            }
+        };
+        println!("{}", name);
+        return Ok(());
+    }
+
+    if let Some(range_txt) = &args.range {
+        let stdout = stdout();
+        let mut out = stdout.lock();
+        let write_err = |err: ::std::io::Error| format!("Could not write output: {}", err);
+        if args.signed {
+            let (start, end, inclusive) = parse_range::<i128>(range_txt)?;
+            let numbers: Box<dyn Iterator<Item = i128>> = if inclusive {
+                Box::new(start..=end)
+            } else {
+                Box::new(start..end)
+            };
+            for nr in numbers {
+                writeln!(out, "{}", encode(signed2unsigned_128(nr))).map_err(write_err)?;
+            }
+        } else {
+            let (start, end, inclusive) = parse_range::<u128>(range_txt)?;
+            if args.scheme == Scheme::Charset {
+                // Iterating over names is faster than encoding each number.
+                let end = if inclusive {
+                    Bound::Included(end)
+                } else {
+                    Bound::Excluded(end)
+                };
+                let names = charset
+                    .names((Bound::Included(start), end))
+                    .ok_or("The names in the range are too long to generate")?;
+                for name in names {
+                    writeln!(out, "{}", show(name)).map_err(write_err)?;
+                }
+            } else {
+                let numbers: Box<dyn Iterator<Item = u128>> = if inclusive {
+                    Box::new(start..=end)
+                } else {
+                    Box::new(start..end)
+                };
+                for nr in numbers {
+                    writeln!(out, "{}", encode(nr)).map_err(write_err)?;
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    let encode_text = |nr_txt: &str| {
         if args.signed {
             let nr: i128 = nr_txt.parse().map_err(|_| {
                 format!(
@@ -61,8 +285,7 @@ fn go(args: &Nr2NameArgs) -> Result<(), String> {
                     &nr_txt
                 )
             })?;
-            let txt = number2name_i128(nr, &charset);
-            println!("{}", txt);
+            Ok(encode(signed2unsigned_128(nr)))
         } else {
             let nr: u128 = nr_txt.parse().map_err(|_| {
                 format!(
@@ -70,10 +293,98 @@ fn go(args: &Nr2NameArgs) -> Result<(), String> {
                     &nr_txt
                 )
             })?;
-            let txt = number2name_u128(nr, &charset);
-            println!("{}", txt);
+            Ok(encode(nr))
         }
+    };
+
+    if let Some(input) = &args.input {
+        return convert_lines(input, encode_text);
+    }
+
+    for nr_txt in &args.names {
+        println!("{}", encode_text(nr_txt)?);
     }
 
     Ok(())
 }
+
+fn inspect(charset: &Charset) {
+    println!("charset: {:?}", charset);
+    println!("size: {}", charset.len());
+    println!();
+    if charset.len() == 1 {
+        println!("each length has one name: the name of number n has n + 1 characters");
+        return;
+    }
+
+    let max_len = charset.max_len_for::<u128>();
+    let rows: Vec<[String; 4]> = (1..=max_len)
+        .map(|length| {
+            let show = |value: Option<u128>| match value {
+                Some(value) => value.to_string(),
+                None => "-".to_owned(),
+            };
+            [
+                length.to_string(),
+                show(charset.count_of_length(length)),
+                show(charset.first_of_length(length)),
+                show(charset.last_of_length(length)),
+            ]
+        })
+        .collect();
+    print_table(&["length", "count", "first", "last"], &rows);
+    println!();
+
+    fn type_row<T: N2NInt>(name: &str, charset: &Charset) -> [String; 2] {
+        [name.to_owned(), charset.max_len_for::<T>().to_string()]
+    }
+    let rows = vec![
+        type_row::<u8>("u8 / i8", charset),
+        type_row::<u16>("u16 / i16", charset),
+        type_row::<u32>("u32 / i32", charset),
+        type_row::<u64>("u64 / i64", charset),
+        type_row::<u128>("u128 / i128", charset),
+    ];
+    print_table(&["type", "max length"], &rows);
+}
+
+fn print_table<const N: usize>(header: &[&str; N], rows: &[[String; N]]) {
+    let mut widths = header.map(str::len);
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let line = |cells: [&str; N]| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+            .collect();
+        println!("{}", padded.join("  "));
+    };
+    line(*header);
+    for row in rows {
+        line(row.each_ref().map(String::as_str));
+    }
+}
+
+/// Parse a range like `3..7` (exclusive) or `3..=7` (inclusive), into start, end and inclusiveness.
+fn parse_range<T: FromStr>(text: &str) -> Result<(T, T, bool), String> {
+    let invalid = || {
+        format!(
+            "The range '{}' was not recognized; expected e.g. '10..20' or '10..=20'",
+            text
+        )
+    };
+    let (start, end, inclusive) = match text.split_once("..=") {
+        Some((start, end)) => (start, end, true),
+        None => {
+            let (start, end) = text.split_once("..").ok_or_else(invalid)?;
+            (start, end, false)
+        }
+    };
+    let start = start.trim().parse().map_err(|_| invalid())?;
+    let end = end.trim().parse().map_err(|_| invalid())?;
+    Ok((start, end, inclusive))
+}
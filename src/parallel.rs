@@ -0,0 +1,93 @@
+use ::rayon::prelude::*;
+
+use crate::int::N2NInt;
+use crate::typ::N2NErr;
+use crate::Charset;
+
+/// The names of the numbers, in the same order, using all cores.
+pub fn par_encode<T: N2NInt + Send + Sync>(numbers: &[T], charset: &Charset) -> Vec<String> {
+    par_encode_iter(numbers.par_iter().copied(), charset).collect()
+}
+
+/// Decode all names using all cores, keeping the order.
+///
+/// If any fail, the error is for the first name that fails (not whichever thread failed first),
+/// together with its index.
+#[allow(clippy::result_large_err)]
+pub fn par_decode<T: N2NInt + Send, S: AsRef<str> + Sync>(
+    names: &[S],
+    charset: &Charset,
+) -> Result<Vec<T>, (usize, N2NErr)> {
+    let decode = |name: &S| T::decode(name.as_ref(), charset);
+    names
+        .par_iter()
+        .map(decode)
+        .collect::<Result<Vec<T>, N2NErr>>()
+        .map_err(|_| {
+            // Failing is rare, so find the first failure only when needed.
+            let index = names
+                .par_iter()
+                .position_first(|name| decode(name).is_err())
+                .expect("a name failed to decode before");
+            let Err(err) = decode(&names[index]) else {
+                unreachable!("the name failed to decode before");
+            };
+            (index, err)
+        })
+}
+
+/// The names of the numbers of a parallel iterator, in the same order.
+pub fn par_encode_iter<'a, T: N2NInt + Send + 'a>(
+    numbers: impl IndexedParallelIterator<Item = T> + 'a,
+    charset: &'a Charset,
+) -> impl IndexedParallelIterator<Item = String> + 'a {
+    numbers.map(move |number| charset.encode_int(number))
+}
+
+/// The numbers of the names of a parallel iterator, in the same order.
+pub fn par_decode_iter<'a, T: N2NInt + Send + 'a, S: AsRef<str> + Send + 'a>(
+    names: impl IndexedParallelIterator<Item = S> + 'a,
+    charset: &'a Charset,
+) -> impl IndexedParallelIterator<Item = Result<T, N2NErr>> + 'a {
+    names.map(move |name| T::decode(name.as_ref(), charset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_order() {
+        let charset = Charset::case_insensitive("abcdefghjkmnpqrstuvwxyz23456789");
+        let numbers: Vec<u64> = (0..20_000).map(|nr| nr * 7919).collect();
+        let names = par_encode(&numbers, &charset);
+        let expected: Vec<String> = numbers.iter().map(|&nr| charset.encode(nr)).collect();
+        assert_eq!(names, expected);
+        assert_eq!(par_decode::<u64, _>(&names, &charset).unwrap(), numbers);
+    }
+
+    #[test]
+    fn first_error() {
+        let charset = Charset::case_sensitive("abc");
+        let mut names: Vec<String> = (0..10_000u32).map(|nr| charset.encode_int(nr)).collect();
+        names[9_000] = "x".to_owned();
+        names[6_000] = "c".repeat(30);
+        names[7_000] = String::new();
+        for _ in 0..10 {
+            match par_decode::<u16, _>(&names, &charset).unwrap_err() {
+                (6_000, N2NErr::TooLarge { .. }) => {}
+                (index, err) => panic!("wrong error at {}: {}", index, err),
+            }
+        }
+    }
+
+    #[test]
+    fn iterators() {
+        let charset = Charset::case_sensitive("abc");
+        let names: Vec<String> = par_encode_iter((0..100u8).into_par_iter(), &charset).collect();
+        assert_eq!(names[13], "aab");
+        let numbers: Result<Vec<u8>, N2NErr> =
+            par_decode_iter(names.par_iter(), &charset).collect();
+        assert_eq!(numbers.unwrap(), (0..100).collect::<Vec<u8>>());
+    }
+}
@@ -0,0 +1,72 @@
+use ::nom::error::{ErrorKind, FromExternalError, ParseError};
+use ::nom::IResult;
+
+use crate::decode::name2number_prefix;
+use crate::int::N2NInt;
+use crate::typ::N2NErr;
+use crate::Charset;
+
+/// A nom parser for a name at the start of the input, like `preceded(tag("order-"), nom_name::<u64, _>(&charset))`.
+///
+/// The name ends at the first character that is not in the charset. Fails with `ErrorKind::TooLarge`
+/// if the number does not fit in the type, and `ErrorKind::Satisfy` if there is no name.
+pub fn nom_name<'a, T: N2NInt, E>(
+    charset: &Charset,
+) -> impl FnMut(&'a str) -> IResult<&'a str, T, E> + '_
+where
+    E: ParseError<&'a str> + FromExternalError<&'a str, N2NErr>,
+{
+    move |input: &'a str| match name2number_prefix(input, charset) {
+        Ok((number, length)) => Ok((&input[length..], number)),
+        Err(err) => {
+            let kind = match err {
+                N2NErr::TooLarge { .. } => ErrorKind::TooLarge,
+                _ => ErrorKind::Satisfy,
+            };
+            Err(::nom::Err::Error(E::from_external_error(input, kind, err)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::nom::bytes::complete::tag;
+    use ::nom::sequence::{preceded, separated_pair};
+    use ::nom::Parser;
+
+    use super::*;
+
+    type Error<'a> = ::nom::error::Error<&'a str>;
+
+    #[test]
+    fn inside_larger_text() {
+        let charset = Charset::case_insensitive("abcdefghjkmnpqrstuvwxyz23456789");
+        let mut parser = separated_pair(
+            preceded(tag("order-"), nom_name::<u64, Error>(&charset)),
+            tag("/line-"),
+            nom_name::<u8, Error>(&charset),
+        );
+        let (rest, (order, line)) = parser.parse("order-ab3k9/line-h!").unwrap();
+        assert_eq!(rest, "!");
+        assert_eq!(order, charset.decode_int::<u64>("ab3k9").unwrap());
+        assert_eq!(line, 7);
+    }
+
+    #[test]
+    fn errors() {
+        let charset = Charset::case_sensitive("ab");
+        let mut parser = nom_name::<u64, Error>(&charset);
+        match parser("-ab") {
+            Err(::nom::Err::Error(err)) => {
+                assert_eq!(err.input, "-ab");
+                assert_eq!(err.code, ErrorKind::Satisfy);
+            }
+            _ => panic!("wrong result"),
+        }
+        let mut parser = nom_name::<u8, Error>(&charset);
+        match parser("bbbbbbbbb-") {
+            Err(::nom::Err::Error(err)) => assert_eq!(err.code, ErrorKind::TooLarge),
+            _ => panic!("wrong result"),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use ::alloc::string::String;
+
+use crate::decode::name2number_alternating;
+use crate::encode::number2name_alternating;
+use crate::signs::{signed2unsigned_128, unsigned2signed_128};
+use crate::typ::N2NErr;
+use crate::Charset;
+
+/// Names that alternate between consonants and vowels, so they can be said out loud (like `bakoda`).
+///
+/// Names always end with a vowel; like charset names, shorter names are for smaller numbers.
+#[derive(Debug, Clone)]
+pub struct Pronounceable {
+    // Vowels first, because the last character is a vowel.
+    charsets: [Charset; 2],
+}
+
+impl Pronounceable {
+    pub fn new(consonants: Charset, vowels: Charset) -> Self {
+        Pronounceable {
+            charsets: [vowels, consonants],
+        }
+    }
+
+    pub fn consonants(&self) -> &Charset {
+        &self.charsets[1]
+    }
+
+    pub fn vowels(&self) -> &Charset {
+        &self.charsets[0]
+    }
+
+    pub fn encode(&self, number: impl Into<u128>) -> String {
+        number2name_alternating(number.into(), &self.charsets)
+    }
+
+    pub fn encode_i128(&self, number: impl Into<i128>) -> String {
+        self.encode(signed2unsigned_128(number.into()))
+    }
+
+    pub fn decode(&self, text: impl AsRef<str>) -> Result<u128, N2NErr> {
+        name2number_alternating(text.as_ref(), &self.charsets)
+    }
+
+    pub fn decode_i128(&self, text: impl AsRef<str>) -> Result<i128, N2NErr> {
+        Ok(unsigned2signed_128(self.decode(text)?))
+    }
+}
+
+impl Default for Pronounceable {
+    /// The 16 consonants and 4 vowels of proquints.
+    fn default() -> Self {
+        Pronounceable::new(
+            Charset::case_insensitive("bdfghjklmnprstvz"),
+            Charset::case_insensitive("aiou"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_names() {
+        let scheme = Pronounceable::default();
+        assert_eq!(scheme.encode(0u8), "a");
+        assert_eq!(scheme.encode(3u8), "u");
+        assert_eq!(scheme.encode(4u8), "ba");
+        assert_eq!(scheme.encode(67u8), "zu");
+        assert_eq!(scheme.encode(68u8), "aba");
+    }
+
+    #[test]
+    fn alternates() {
+        let scheme = Pronounceable::default();
+        let name = scheme.encode(u128::MAX);
+        for (position, character) in name.chars().rev().enumerate() {
+            let charset = if position % 2 == 0 {
+                scheme.vowels()
+            } else {
+                scheme.consonants()
+            };
+            assert!(charset.index_of(character).is_some());
+        }
+    }
+
+    #[test]
+    fn roundtrip() -> Result<(), N2NErr> {
+        let scheme = Pronounceable::new(
+            Charset::case_sensitive("bdgklmnprstvz"),
+            Charset::case_sensitive("aeiou"),
+        );
+        for number in (0..5000).chain([u64::MAX as u128, u128::MAX - 1, u128::MAX]) {
+            assert_eq!(scheme.decode(scheme.encode(number))?, number);
+        }
+        for number in [0, -1, 1, i128::MIN, i128::MAX] {
+            assert_eq!(scheme.decode_i128(scheme.encode_i128(number))?, number);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn case_insensitive() -> Result<(), N2NErr> {
+        let scheme = Pronounceable::default();
+        assert_eq!(scheme.decode("BaKoDa")?, scheme.decode("bakoda")?);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid() {
+        let scheme = Pronounceable::default();
+        match scheme.decode("bab").unwrap_err() {
+            N2NErr::InvalidCharacter { character, .. } => assert_eq!(character, 'b'),
+            _ => panic!("wrong error"),
+        }
+        match scheme.decode("baxa").unwrap_err() {
+            N2NErr::InvalidCharacter {
+                character,
+                position,
+                charset,
+                ..
+            } => {
+                assert_eq!(character, 'x');
+                assert_eq!(position, 2);
+                assert!(charset.index_of('b').is_some());
+            }
+            _ => panic!("wrong error"),
+        }
+        assert_eq!(
+            scheme
+                .decode("xaxa")
+                .unwrap_err()
+                .invalid_characters()
+                .len(),
+            2
+        );
+        match scheme.decode("").unwrap_err() {
+            N2NErr::EmptyInput => {}
+            _ => panic!("wrong error"),
+        }
+        match scheme.decode("za".repeat(40)).unwrap_err() {
+            N2NErr::TooLarge { charset, max } => {
+                assert!(charset.is_none());
+                assert_eq!(max, u128::MAX);
+            }
+            _ => panic!("wrong error"),
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use ::alloc::string::String;
+
+use crate::typ::N2NErr;
+use crate::util::trimmed_range;
+use crate::Charset;
+
+// Proquints spell each 16 bits as consonant-vowel-consonant-vowel-consonant,
+// see https://arxiv.org/abs/0901.4016 . Unlike charset names, all proquints of a type have the same length.
+
+const CONSONANTS: &[u8; 16] = b"bdfghjklmnprstvz";
+const VOWELS: &[u8; 4] = b"aiou";
+
+fn push_word(text: &mut String, word: u16) {
+    let word = word as usize;
+    text.push(CONSONANTS[(word >> 12) & 0xf] as char);
+    text.push(VOWELS[(word >> 10) & 0x3] as char);
+    text.push(CONSONANTS[(word >> 6) & 0xf] as char);
+    text.push(VOWELS[(word >> 4) & 0x3] as char);
+    text.push(CONSONANTS[word & 0xf] as char);
+}
+
+fn letter_value(character: char, letters: &[u8]) -> Option<u128> {
+    let lower = character.to_ascii_lowercase();
+    letters
+        .iter()
+        .position(|&letter| letter as char == lower)
+        .map(|value| value as u128)
+}
+
+/// The letters that are allowed at this position of a proquint (`-` between words).
+fn letters_at(position: usize) -> &'static [u8] {
+    match position % 6 {
+        0 | 2 | 4 => CONSONANTS,
+        1 | 3 => VOWELS,
+        _ => b"-",
+    }
+}
+
+/// Decode dash-separated proquint words, which should be exactly `words` long.
+fn decode_words(original: &str, words: usize) -> Result<u128, N2NErr> {
+    let text = original.trim();
+    let expected = words * 6 - 1;
+    let actual = text.chars().count();
+    if actual == 0 {
+        return Err(N2NErr::EmptyInput);
+    }
+    if actual != expected {
+        return Err(N2NErr::InvalidLength { expected, actual });
+    }
+    let mut number: u128 = 0;
+    for (position, character) in text.chars().enumerate() {
+        let letters = letters_at(position);
+        number = match letter_value(character, letters) {
+            Some(_) if letters.len() == 1 => number,
+            Some(value) => (number << letters.len().trailing_zeros()) | value,
+            None => {
+                let (start, end) = trimmed_range(original);
+                let charset = Charset::case_insensitive(String::from_utf8_lossy(letters));
+                return Err(N2NErr::invalid_in(original, &charset, |index, c| {
+                    index < start
+                        || index >= end
+                        || letter_value(c, letters_at(index - start)).is_some()
+                }));
+            }
+        };
+    }
+    Ok(number)
+}
+
+macro_rules! proquint_for_type {
+    ($encode: ident, $decode: ident, $int:ty) => {
+        /// Convert a number to proquint words of 16 bits, separated by dashes.
+        pub fn $encode(number: impl Into<$int>) -> String {
+            let number = number.into();
+            let words = <$int>::BITS as usize / 16;
+            let mut text = String::with_capacity(words * 6 - 1);
+            for index in (0..words).rev() {
+                if index + 1 < words {
+                    text.push('-');
+                }
+                push_word(&mut text, (number as u128 >> (16 * index)) as u16);
+            }
+            text
+        }
+
+        /// Convert proquint words back to the number, ignoring case and surrounding whitespace.
+        pub fn $decode(text: impl AsRef<str>) -> Result<$int, N2NErr> {
+            Ok(decode_words(text.as_ref(), <$int>::BITS as usize / 16)? as $int)
+        }
+    };
+}
+
+proquint_for_type!(number2proquint_u16, proquint2number_u16, u16);
+proquint_for_type!(number2proquint_u32, proquint2number_u32, u32);
+proquint_for_type!(number2proquint_u64, proquint2number_u64, u64);
+proquint_for_type!(number2proquint_u128, proquint2number_u128, u128);
+
+/// Convert a number to proquint words with as few words as its type would need (1, 2, 4 or 8 words).
+pub fn number2proquint(number: impl Into<u128>) -> String {
+    let number = number.into();
+    if let Ok(number) = u16::try_from(number) {
+        number2proquint_u16(number)
+    } else if let Ok(number) = u32::try_from(number) {
+        number2proquint_u32(number)
+    } else if let Ok(number) = u64::try_from(number) {
+        number2proquint_u64(number)
+    } else {
+        number2proquint_u128(number)
+    }
+}
+
+/// Convert proquint words back to the number, for 1, 2, 4 or 8 words (like `number2proquint` makes).
+/// Other lengths fail with `InvalidLength`, expecting the next valid number of words.
+pub fn proquint2number(text: impl AsRef<str>) -> Result<u128, N2NErr> {
+    let text = text.as_ref();
+    let words = (text.trim().chars().count() + 1)
+        .div_ceil(6)
+        .next_power_of_two()
+        .min(8);
+    decode_words(text, words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_values() {
+        // IPv4 addresses from the proquint proposal.
+        assert_eq!(number2proquint_u32(0x7f000001u32), "lusab-babad");
+        assert_eq!(number2proquint_u32(0x3f54dcc1u32), "gutih-tugad");
+        assert_eq!(number2proquint_u32(0x3f760723u32), "gutuk-bisog");
+        assert_eq!(number2proquint_u32(0x8c62c18du32), "mudof-sakat");
+        assert_eq!(number2proquint_u16(0u16), "babab");
+        assert_eq!(number2proquint_u16(u16::MAX), "zuzuz");
+    }
+
+    #[test]
+    fn widths() {
+        assert_eq!(number2proquint_u64(1u8), "babab-babab-babab-babad");
+        assert_eq!(number2proquint_u128(u128::MAX), ["zuzuz"; 8].join("-"));
+    }
+
+    #[test]
+    fn any_width() -> Result<(), N2NErr> {
+        assert_eq!(number2proquint(0u8), "babab");
+        assert_eq!(number2proquint(0x7f000001u32), "lusab-babad");
+        assert_eq!(number2proquint(1u128 << 32), "babab-babad-babab-babab");
+        assert_eq!(proquint2number("lusab-babad")?, 0x7f000001);
+        assert_eq!(proquint2number("babab-babab-babab-babad")?, 1);
+        assert_eq!(proquint2number(number2proquint(u128::MAX))?, u128::MAX);
+        match proquint2number("lusab-bab").unwrap_err() {
+            N2NErr::InvalidLength { expected, actual } => {
+                assert_eq!(expected, 11);
+                assert_eq!(actual, 9);
+            }
+            _ => panic!("wrong error"),
+        }
+        match proquint2number("babab-babab-babad").unwrap_err() {
+            N2NErr::InvalidLength { expected, actual } => {
+                assert_eq!(expected, 23);
+                assert_eq!(actual, 17);
+            }
+            _ => panic!("wrong error"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> Result<(), N2NErr> {
+        for number in [0, 1, 0x7f000001, 0xdead_beef, u32::MAX] {
+            assert_eq!(proquint2number_u32(number2proquint_u32(number))?, number);
+        }
+        for number in [0, 12345678901234567890, u64::MAX] {
+            assert_eq!(proquint2number_u64(number2proquint_u64(number))?, number);
+        }
+        let number = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+        assert_eq!(proquint2number_u128(number2proquint_u128(number))?, number);
+        assert_eq!(proquint2number_u16(" ZUZUZ\n")?, u16::MAX);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid() {
+        match proquint2number_u32("lusab").unwrap_err() {
+            N2NErr::InvalidLength { expected, actual } => {
+                assert_eq!(expected, 11);
+                assert_eq!(actual, 5);
+            }
+            _ => panic!("wrong error"),
+        }
+        match proquint2number_u32("lusab_babad").unwrap_err() {
+            N2NErr::InvalidCharacter { character, .. } => assert_eq!(character, '_'),
+            _ => panic!("wrong error"),
+        }
+        match proquint2number_u32(" lusab-bebad").unwrap_err() {
+            N2NErr::InvalidCharacter {
+                character,
+                position,
+                ..
+            } => {
+                assert_eq!(character, 'e');
+                assert_eq!(position, 8);
+            }
+            _ => panic!("wrong error"),
+        }
+        match proquint2number_u16("").unwrap_err() {
+            N2NErr::EmptyInput => {}
+            _ => panic!("wrong error"),
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use ::std::io;
+use ::std::ops::{Bound, RangeBounds};
+
+use crate::Charset;
+
+/// Random bytes from the operating system, fetched in blocks to avoid a system call per character.
+struct OsRandom {
+    buffer: [u8; 128],
+    position: usize,
+}
+
+impl OsRandom {
+    fn new() -> Self {
+        OsRandom {
+            buffer: [0; 128],
+            position: 128,
+        }
+    }
+
+    fn fill(&mut self, target: &mut [u8]) -> io::Result<()> {
+        for byte in target {
+            if self.position == self.buffer.len() {
+                getrandom::fill(&mut self.buffer)?;
+                self.position = 0;
+            }
+            *byte = self.buffer[self.position];
+            self.position += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Number of characters needed for at least `bits` of entropy, i.e. the smallest length
+/// for which `size^length >= 2^bits`. Computed exactly, without floating point.
+/// Panics if `size` is less than two.
+pub(crate) fn token_len(bits: u32, size: usize) -> usize {
+    assert!(
+        size > 1,
+        "random tokens need a charset with at least two characters"
+    );
+    if bits == 0 {
+        return 0;
+    }
+    // Little-endian 32-bit limbs of size^length.
+    let mut power: Vec<u64> = vec![1];
+    let mut length = 0;
+    loop {
+        length += 1;
+        let mut carry = 0;
+        for limb in power.iter_mut() {
+            let product = *limb * size as u64 + carry;
+            *limb = product & 0xffff_ffff;
+            carry = product >> 32;
+        }
+        if carry > 0 {
+            power.push(carry);
+        }
+        let top = *power.last().unwrap();
+        let bit_length = (power.len() as u64 - 1) * 32 + (64 - top.leading_zeros() as u64);
+        if bit_length > bits as u64 {
+            return length;
+        }
+    }
+}
+
+/// Uniformly random number below `bound` (or any u128 if `bound` is zero), without modulo bias.
+fn uniform_below(
+    bound: u128,
+    fill: &mut impl FnMut(&mut [u8]) -> io::Result<()>,
+) -> io::Result<u128> {
+    let bits = if bound == 0 {
+        128
+    } else {
+        128 - (bound - 1).leading_zeros()
+    };
+    let byte_count = bits.div_ceil(8) as usize;
+    let mask = if bits == 128 {
+        u128::MAX
+    } else {
+        (1u128 << bits) - 1
+    };
+    let mut bytes = [0u8; 16];
+    loop {
+        fill(&mut bytes[..byte_count])?;
+        let candidate = u128::from_le_bytes(bytes) & mask;
+        // Rejecting values outside the range (at most half of them) keeps the rest uniform.
+        if bound == 0 || candidate < bound {
+            return Ok(candidate);
+        }
+    }
+}
+
+pub(crate) fn random_token_with(
+    bits: u32,
+    charset: &Charset,
+    fill: &mut impl FnMut(&mut [u8]) -> io::Result<()>,
+) -> io::Result<String> {
+    if charset.len() < 2 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "random tokens need a charset with at least two characters",
+        ));
+    }
+    if bits == 0 {
+        // The token would be empty, which is not a name.
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "random tokens need at least one bit of entropy",
+        ));
+    }
+    let length = token_len(bits, charset.len());
+    let mut token = String::with_capacity(length);
+    for _ in 0..length {
+        let index = uniform_below(charset.len() as u128, fill)?;
+        token.push(charset[index as usize]);
+    }
+    Ok(token)
+}
+
+pub(crate) fn random_number_with(
+    range: impl RangeBounds<u128>,
+    fill: &mut impl FnMut(&mut [u8]) -> io::Result<()>,
+) -> io::Result<u128> {
+    let first = match range.start_bound() {
+        Bound::Included(&start) => Some(start),
+        Bound::Excluded(&start) => start.checked_add(1),
+        Bound::Unbounded => Some(0),
+    };
+    let last = match range.end_bound() {
+        Bound::Included(&end) => Some(end),
+        Bound::Excluded(&end) => end.checked_sub(1),
+        Bound::Unbounded => Some(u128::MAX),
+    };
+    match (first, last) {
+        (Some(first), Some(last)) if first <= last => {
+            // A span of zero means the whole u128 range, which does not fit.
+            let span = (last - first).wrapping_add(1);
+            Ok(first + uniform_below(span, fill)?)
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "cannot pick a random number from an empty range",
+        )),
+    }
+}
+
+/// A uniformly random name with at least `bits` of entropy, using the operating system's secure random source.
+/// All tokens for the same `bits` and charset have the same, minimal length.
+/// Fails for a charset with a single character, which has no entropy, and for zero bits.
+pub(crate) fn random_token(bits: u32, charset: &Charset) -> io::Result<String> {
+    let mut source = OsRandom::new();
+    random_token_with(bits, charset, &mut |target| source.fill(target))
+}
+
+/// A uniformly random number in the range, using the operating system's secure random source.
+pub fn random_number(range: impl RangeBounds<u128>) -> io::Result<u128> {
+    let mut source = OsRandom::new();
+    random_number_with(range, &mut |target| source.fill(target))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Source that returns the given bytes, repeating them as needed.
+    fn fixed(bytes: &[u8]) -> impl FnMut(&mut [u8]) -> io::Result<()> + '_ {
+        let mut position = 0;
+        move |target: &mut [u8]| {
+            for byte in target {
+                *byte = bytes[position % bytes.len()];
+                position += 1;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn lengths() {
+        assert_eq!(token_len(0, 32), 0);
+        assert_eq!(token_len(1, 2), 1);
+        assert_eq!(token_len(128, 16), 32);
+        assert_eq!(token_len(128, 64), 22);
+        assert_eq!(token_len(128, 31), 26);
+        assert_eq!(token_len(256, 31), 52);
+        assert_eq!(token_len(5, 32), 1);
+        assert_eq!(token_len(6, 32), 2);
+        // 3^5 = 243 < 256 <= 729 = 3^6
+        assert_eq!(token_len(8, 3), 6);
+    }
+
+    #[test]
+    fn rejection() -> io::Result<()> {
+        let charset = Charset::case_sensitive("abc");
+        // Two bits per character; 3 is rejected.
+        let token = random_token_with(8, &charset, &mut fixed(&[3, 0, 3, 3, 1, 2]))?;
+        assert_eq!(token, "abcabc");
+        Ok(())
+    }
+
+    #[test]
+    fn single_character() {
+        let charset = Charset::case_sensitive("a");
+        let err = random_token_with(8, &charset, &mut fixed(&[0])).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn zero_bits() {
+        let charset = Charset::case_sensitive("abc");
+        let err = random_token_with(0, &charset, &mut fixed(&[0])).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn numbers() -> io::Result<()> {
+        assert_eq!(random_number_with(10..=13, &mut fixed(&[7]))?, 13);
+        assert_eq!(random_number_with(5..6, &mut fixed(&[255]))?, 5);
+        assert_eq!(random_number_with(.., &mut fixed(&[255]))?, u128::MAX);
+        assert!(random_number_with(5..5, &mut fixed(&[0])).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn os_random() -> io::Result<()> {
+        let charset = Charset::case_insensitive("abcdefghjkmnpqrstuvwxyz23456789");
+        let first = random_token(128, &charset)?;
+        let second = random_token(128, &charset)?;
+        assert_eq!(first.chars().count(), 26);
+        assert_ne!(first, second);
+        assert!(first
+            .chars()
+            .all(|character| charset.index_of(character).is_some()));
+        let number = random_number(1000..2000)?;
+        assert!((1000..2000).contains(&number));
+        Ok(())
+    }
+}
@@ -0,0 +1,236 @@
+use ::alloc::boxed::Box;
+use ::alloc::string::String;
+use ::core::iter;
+use ::core::ops::Range;
+
+use crate::int::N2NInt;
+use crate::Charset;
+
+/// Decides whether a name that was found should be kept.
+type Check<'a> = Box<dyn Fn(&str) -> bool + 'a>;
+
+/// A name found in text by `Scanner`, with its decoded number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found<T> {
+    /// Byte range of the match in the text, including the prefix.
+    pub span: Range<usize>,
+    pub value: T,
+}
+
+/// Finds names in free text, like ids mentioned in a support ticket.
+///
+/// Names are runs of charset characters, so they end at the first character that is not in the charset.
+/// They can be part of a longer word (like `aab` in `xaab` for charset `abc`), unless `whole_words` is set.
+/// Names that do not meet the constraints, do not pass the check or do not fit the type are skipped.
+pub struct Scanner<'a> {
+    charset: &'a Charset,
+    prefix: String,
+    min_length: usize,
+    max_length: usize,
+    whole_words: bool,
+    check: Option<Check<'a>>,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(charset: &'a Charset) -> Self {
+        Scanner {
+            charset,
+            prefix: String::new(),
+            min_length: 1,
+            max_length: usize::MAX,
+            whole_words: false,
+            check: None,
+        }
+    }
+
+    /// Only find names directly after this text, like `order-`.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Skip names with fewer characters, to avoid matching short words.
+    pub fn min_length(mut self, length: usize) -> Self {
+        self.min_length = length.max(1);
+        self
+    }
+
+    /// Skip names with more characters.
+    pub fn max_length(mut self, length: usize) -> Self {
+        self.max_length = length;
+        self
+    }
+
+    /// Skip names (including prefix) that are directly next to a letter or digit that is not part of them.
+    pub fn whole_words(mut self, whole_words: bool) -> Self {
+        self.whole_words = whole_words;
+        self
+    }
+
+    /// Skip names for which this returns false, e.g. because a check symbol does not match.
+    pub fn check(mut self, check: impl Fn(&str) -> bool + 'a) -> Self {
+        self.check = Some(Box::new(check));
+        self
+    }
+
+    /// All names in the text that can be decoded to the type, in order.
+    pub fn scan<'s, T: N2NInt + 's>(
+        &'s self,
+        text: &'s str,
+    ) -> impl Iterator<Item = Found<T>> + 's {
+        let mut offset = 0;
+        iter::from_fn(move || {
+            while offset < text.len() {
+                let (start, name) = self.next_candidate(text, offset)?;
+                let end = name.end;
+                offset = end.max(offset + 1);
+                if self.whole_words && !is_whole_word(text, start..end) {
+                    continue;
+                }
+                if let Some(value) = self.accept(&text[name]) {
+                    return Some(Found {
+                        span: start..end,
+                        value,
+                    });
+                }
+            }
+            None
+        })
+    }
+
+    /// The start of the next match (including prefix) and the byte range of its name.
+    fn next_candidate(&self, text: &str, offset: usize) -> Option<(usize, Range<usize>)> {
+        let is_name_char = |character: char| self.charset.index_of(character).is_some();
+        let mut search = offset;
+        loop {
+            let start = if self.prefix.is_empty() {
+                search + text[search..].find(is_name_char)?
+            } else {
+                search + text[search..].find(self.prefix.as_str())?
+            };
+            let name_start = start + self.prefix.len();
+            let name_end = text[name_start..]
+                .find(|character| !is_name_char(character))
+                .map_or(text.len(), |length| name_start + length);
+            if name_end > name_start {
+                return Some((start, name_start..name_end));
+            }
+            // A prefix without a name; the prefix is not empty, so this makes progress.
+            search = name_start;
+        }
+    }
+
+    fn accept<T: N2NInt>(&self, name: &str) -> Option<T> {
+        let length = name.chars().count();
+        if length < self.min_length || length > self.max_length {
+            return None;
+        }
+        if let Some(check) = &self.check {
+            if !check(name) {
+                return None;
+            }
+        }
+        T::decode(name, self.charset).ok()
+    }
+}
+
+/// Whether the match is not directly preceded or followed by a letter or digit.
+fn is_whole_word(text: &str, span: Range<usize>) -> bool {
+    let before = text[..span.start].chars().next_back();
+    let after = text[span.end..].chars().next();
+    !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+}
+
+#[cfg(test)]
+mod tests {
+    use ::alloc::vec;
+    use ::alloc::vec::Vec;
+
+    use super::*;
+
+    fn charset() -> Charset {
+        Charset::case_insensitive("abcdefghjkmnpqrstuvwxyz23456789")
+    }
+
+    #[test]
+    fn all_runs() {
+        let charset = Charset::case_sensitive("abc");
+        let found: Vec<Found<u32>> = Scanner::new(&charset).scan("xaab, ba!c").collect();
+        assert_eq!(
+            found,
+            [
+                Found {
+                    span: 1..4,
+                    value: 13
+                },
+                Found {
+                    span: 6..8,
+                    value: 6
+                },
+                Found {
+                    span: 9..10,
+                    value: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn whole_words() {
+        let charset = Charset::case_sensitive("abc");
+        let scanner = Scanner::new(&charset).whole_words(true);
+        let found: Vec<Found<u32>> = scanner.scan("xaab, ba!c cab1 é_b").collect();
+        let spans: Vec<Range<usize>> = found.into_iter().map(|found| found.span).collect();
+        assert_eq!(spans, [6..8, 9..10, 19..20]);
+        let prefixed = Scanner::new(&charset).prefix("id-").whole_words(true);
+        let found: Vec<Found<u32>> = prefixed.scan("id-ab xid-ab id-abx").collect();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].span, 0..5);
+    }
+
+    #[test]
+    fn prefix_and_length() {
+        let charset = charset();
+        let text = "Re: order-ab3k9 and order-7, order-zz or order-?";
+        let scanner = Scanner::new(&charset).prefix("order-").min_length(2);
+        let found: Vec<Found<u64>> = scanner.scan(text).collect();
+        assert_eq!(found.len(), 2);
+        assert_eq!(&text[found[0].span.clone()], "order-ab3k9");
+        assert_eq!(found[0].value, charset.decode_int::<u64>("ab3k9").unwrap());
+        assert_eq!(&text[found[1].span.clone()], "order-zz");
+        let scanner = Scanner::new(&charset).prefix("order-").max_length(2);
+        let found: Vec<Found<u64>> = scanner.scan(text).collect();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].value, charset.decode_int::<u64>("7").unwrap());
+    }
+
+    #[test]
+    fn skip_too_large_and_failed_check() {
+        let charset = charset();
+        let text = format!("{} ab zz x9 é", charset.encode_int(70_000u32));
+        let scanner = Scanner::new(&charset).check(|name| name != "ab");
+        let values: Vec<u16> = scanner.scan(&text).map(|found| found.value).collect();
+        let expected: Vec<u16> = vec![
+            charset.decode_int("zz").unwrap(),
+            charset.decode_int("x9").unwrap(),
+        ];
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn unicode() {
+        let charset = Charset::case_sensitive("一二三");
+        let found: Vec<Found<u8>> = Scanner::new(&charset).scan("é一二 x三").collect();
+        assert_eq!(found[0].span, 2..8);
+        assert_eq!(found[0].value, 4);
+        assert_eq!(found[1].span, 10..13);
+    }
+
+    #[test]
+    fn multi_character_lowercase() {
+        let charset = Charset::case_insensitive("abc");
+        let found: Vec<Found<u8>> = Scanner::new(&charset).scan("İab İ").collect();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].span, 2..4);
+    }
+}
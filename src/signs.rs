@@ -91,6 +91,7 @@ signed2unsigned_for_type!(
 /// +2 -> 4
 /// +3 -> 6
 /// +4 -> 8
+#[allow(clippy::doc_lazy_continuation)]
 pub fn signed2unsigned(number: i64) -> u64 {
     signed2unsigned_64(number)
 }
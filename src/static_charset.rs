@@ -0,0 +1,350 @@
+use ::core::cmp::Ordering;
+use ::core::fmt;
+use ::core::hash::{Hash, Hasher};
+use ::core::marker::PhantomData;
+use ::core::str::FromStr;
+
+use crate::decode::{append_digit_u128, append_digit_u64};
+use crate::int::N2NInt;
+use crate::typ::N2NErr;
+use crate::util::try_lower;
+use crate::Charset;
+
+/// Marks ASCII characters that are not in the charset, in `StaticCharset::ASCII_INDEX`.
+const NOT_IN_CHARSET: u8 = u8::MAX;
+
+/// The index of each ASCII character in the characters, for `StaticCharset::ASCII_INDEX`.
+/// Panics (at compile time) for duplicates, more than 255 characters, or (if case-insensitive)
+/// characters with an ASCII or multi-character lower case.
+const fn ascii_index(chars: &[char], case_insensitive: bool) -> [u8; 128] {
+    assert!(
+        chars.len() < NOT_IN_CHARSET as usize,
+        "static charsets have at most 255 characters"
+    );
+    let mut index = [NOT_IN_CHARSET; 128];
+    let mut position = 0;
+    while position < chars.len() {
+        let character = chars[position];
+        // The only non-ASCII characters whose lower case has an ASCII character.
+        assert!(
+            !case_insensitive || !matches!(character, '\u{130}' | '\u{212A}'),
+            "case-insensitive static charsets cannot contain the Kelvin sign or dotted capital I"
+        );
+        if character.is_ascii() {
+            let (first, second) = if case_insensitive {
+                (
+                    character.to_ascii_lowercase(),
+                    character.to_ascii_uppercase(),
+                )
+            } else {
+                (character, character)
+            };
+            assert!(
+                index[first as usize] == NOT_IN_CHARSET && index[second as usize] == NOT_IN_CHARSET,
+                "static charset contains a character more than once"
+            );
+            index[first as usize] = position as u8;
+            index[second as usize] = position as u8;
+        }
+        position += 1;
+    }
+    index
+}
+
+/// A charset that is known at compile time, implemented by zero-sized types like `Base32Human`.
+///
+/// Encoding and decoding are specialized for each charset, without looking up characters in a `Charset`.
+/// In case-insensitive charsets, non-ASCII characters must not have an ASCII lower case (like the Kelvin sign).
+pub trait StaticCharset {
+    /// The characters, in order.
+    const CHARS: &'static [char];
+
+    /// Whether upper and lower case are accepted as the same character when decoding.
+    const CASE_INSENSITIVE: bool;
+
+    /// The index of each ASCII character, or 255 if it is not in the charset. Computed from `CHARS`.
+    const ASCII_INDEX: [u8; 128] = ascii_index(Self::CHARS, Self::CASE_INSENSITIVE);
+
+    /// The same characters as a `Charset`, which is used in errors.
+    fn charset() -> &'static Charset;
+
+    /// The index of the character, or None if it is not in the charset.
+    #[inline]
+    fn index_of(character: char) -> Option<u64> {
+        if character.is_ascii() {
+            let index = Self::ASCII_INDEX[character as usize];
+            return (index != NOT_IN_CHARSET).then_some(index as u64);
+        }
+        let position = if Self::CASE_INSENSITIVE {
+            let character = try_lower(character)?;
+            Self::CHARS
+                .iter()
+                .position(|&c| try_lower(c) == Some(character))
+        } else {
+            Self::CHARS.iter().position(|&c| c == character)
+        };
+        position.map(|position| position as u64)
+    }
+
+    /// Write the name of the number; the same as `Self::charset().encode_into`.
+    #[inline]
+    fn encode_into<T: N2NInt>(number: T, out: &mut impl fmt::Write) -> fmt::Result {
+        let number = number.to_encoded();
+        match u64::try_from(number) {
+            Ok(number) => encode_u64::<Self>(number, out),
+            Err(_) => encode_u128::<Self>(number, out),
+        }
+    }
+
+    /// Convert a name back to a number; the same as `Self::charset().decode`.
+    #[inline]
+    fn decode<T: N2NInt>(text: &str) -> Result<T, N2NErr> {
+        let size = Self::CHARS.len() as u64;
+        let invalid =
+            || N2NErr::invalid_in(text, Self::charset(), |_, c| Self::index_of(c).is_some());
+        let too_large = || {
+            // Invalid characters further on are reported instead, like for `Charset::decode`.
+            if text.chars().any(|c| Self::index_of(c).is_none()) {
+                return invalid();
+            }
+            N2NErr::TooLarge {
+                charset: Some(Self::charset().clone()),
+                max: T::ENCODED_MAX,
+            }
+        };
+        let mut chars = text.chars();
+        // Most names fit in a u64, which is faster; continue as u128 if not.
+        let mut narrow = None;
+        let mut number = loop {
+            let Some(character) = chars.next() else {
+                break narrow.map(u128::from).ok_or(N2NErr::EmptyInput)?;
+            };
+            let digit = Self::index_of(character).ok_or_else(invalid)?;
+            match append_digit_u64(narrow, digit, size) {
+                Some(next) => narrow = Some(next),
+                None => {
+                    break append_digit_u128(narrow.map(u128::from), digit, size)
+                        .ok_or_else(too_large)?
+                }
+            }
+        };
+        for character in chars {
+            let digit = Self::index_of(character).ok_or_else(invalid)?;
+            number = append_digit_u128(Some(number), digit, size).ok_or_else(too_large)?;
+        }
+        T::from_encoded(number).ok_or_else(too_large)
+    }
+}
+
+macro_rules! encode_static_for_type {
+    ($name: ident, $int:ty) => {
+        /// Like `number2name_into_*`, but the size and characters are constants.
+        #[inline]
+        fn $name<S: StaticCharset + ?Sized>(
+            mut remainder: $int,
+            out: &mut impl fmt::Write,
+        ) -> fmt::Result {
+            let size = S::CHARS.len() as $int;
+            if size == 1 {
+                for _ in 0..=remainder {
+                    out.write_char(S::CHARS[0])?;
+                }
+                return Ok(());
+            }
+            let mut name = [0u8; 4 * <$int>::BITS as usize];
+            let mut start = name.len();
+            loop {
+                let character = S::CHARS[(remainder % size) as usize];
+                start -= character.len_utf8();
+                character.encode_utf8(&mut name[start..]);
+                remainder /= size;
+                if remainder == 0 {
+                    break;
+                }
+                remainder -= 1;
+            }
+            out.write_str(::core::str::from_utf8(&name[start..]).expect("name is valid UTF-8"))
+        }
+    };
+}
+
+encode_static_for_type!(encode_u64, u64);
+encode_static_for_type!(encode_u128, u128);
+
+/// A number that is written as a name in the charset `S`, like `Name<u64, Base32Human>`.
+///
+/// Like `Charset::encode` and `Charset::decode`, but the charset is part of the type.
+pub struct Name<T: N2NInt, S: StaticCharset> {
+    number: T,
+    charset: PhantomData<fn() -> S>,
+}
+
+impl<T: N2NInt, S: StaticCharset> Name<T, S> {
+    pub fn new(number: T) -> Self {
+        Name {
+            number,
+            charset: PhantomData,
+        }
+    }
+
+    pub fn number(&self) -> T {
+        self.number
+    }
+}
+
+impl<T: N2NInt, S: StaticCharset> fmt::Display for Name<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        S::encode_into(self.number, f)
+    }
+}
+
+impl<T: N2NInt + fmt::Debug, S: StaticCharset> fmt::Debug for Name<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Name({}, {:?})", self, self.number)
+    }
+}
+
+impl<T: N2NInt, S: StaticCharset> FromStr for Name<T, S> {
+    type Err = N2NErr;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        S::decode(text).map(Name::new)
+    }
+}
+
+// Implemented by hand, because deriving would require the charset to implement them too.
+
+impl<T: N2NInt, S: StaticCharset> Clone for Name<T, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: N2NInt, S: StaticCharset> Copy for Name<T, S> {}
+
+impl<T: N2NInt + PartialEq, S: StaticCharset> PartialEq for Name<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.number == other.number
+    }
+}
+
+impl<T: N2NInt + Eq, S: StaticCharset> Eq for Name<T, S> {}
+
+impl<T: N2NInt + Ord, S: StaticCharset> PartialOrd for Name<T, S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: N2NInt + Ord, S: StaticCharset> Ord for Name<T, S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.number.cmp(&other.number)
+    }
+}
+
+impl<T: N2NInt + Hash, S: StaticCharset> Hash for Name<T, S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.number.hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::std::collections::HashSet;
+    use ::std::string::ToString;
+    use ::std::sync::OnceLock;
+
+    use super::*;
+
+    struct Abc;
+
+    impl StaticCharset for Abc {
+        const CHARS: &'static [char] = &['a', 'B', 'ç'];
+        const CASE_INSENSITIVE: bool = true;
+
+        fn charset() -> &'static Charset {
+            static CHARSET: OnceLock<Charset> = OnceLock::new();
+            CHARSET.get_or_init(|| Charset::case_insensitive("aBç"))
+        }
+    }
+
+    struct One;
+
+    impl StaticCharset for One {
+        const CHARS: &'static [char] = &['x'];
+        const CASE_INSENSITIVE: bool = false;
+
+        fn charset() -> &'static Charset {
+            static CHARSET: OnceLock<Charset> = OnceLock::new();
+            CHARSET.get_or_init(|| Charset::case_sensitive("x"))
+        }
+    }
+
+    #[test]
+    fn same_as_charset() -> Result<(), N2NErr> {
+        let charset = Abc::charset();
+        for number in (0..200).chain([u64::MAX - 1, u64::MAX]) {
+            let name = Name::<u64, Abc>::new(number).to_string();
+            assert_eq!(name, charset.encode(number));
+            assert_eq!(
+                name.to_uppercase().parse::<Name<u64, Abc>>()?.number(),
+                number
+            );
+        }
+        for number in [u64::MAX as u128 + 1, u128::MAX] {
+            let name = Name::<u128, Abc>::new(number).to_string();
+            assert_eq!(name, charset.encode_int(number));
+            assert_eq!(name.parse::<Name<u128, Abc>>()?.number(), number);
+        }
+        assert_eq!(Name::<i32, Abc>::new(-1).to_string(), "B");
+        assert_eq!(Name::<u8, One>::new(2).to_string(), "xxx");
+        assert_eq!(One::decode::<u8>("xxx")?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn errors() {
+        match Abc::decode::<u64>("").unwrap_err() {
+            N2NErr::EmptyInput => {}
+            _ => panic!("wrong error"),
+        }
+        match Abc::decode::<u64>("aÇd!").unwrap_err() {
+            N2NErr::InvalidCharacter { position, more, .. } => {
+                assert_eq!(position, 2);
+                assert_eq!(more.len(), 1);
+            }
+            _ => panic!("wrong error"),
+        }
+        let too_large = Abc::charset().encode_int(256u16);
+        match Abc::decode::<u8>(&too_large).unwrap_err() {
+            N2NErr::TooLarge { max, .. } => assert_eq!(max, 255),
+            _ => panic!("wrong error"),
+        }
+        match Abc::decode::<u128>(&"ç".repeat(100)).unwrap_err() {
+            N2NErr::TooLarge { max, .. } => assert_eq!(max, u128::MAX),
+            _ => panic!("wrong error"),
+        }
+        match Abc::decode::<u64>("aİ").unwrap_err() {
+            N2NErr::InvalidCharacter { character, .. } => assert_eq!(character, 'İ'),
+            _ => panic!("wrong error"),
+        }
+        match Abc::decode::<u128>(&format!("{}!", "ç".repeat(100))).unwrap_err() {
+            N2NErr::InvalidCharacter { position, .. } => assert_eq!(position, 100),
+            _ => panic!("wrong error"),
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn ascii_lower_case() {
+        ascii_index(&['a', '\u{212A}'], true);
+    }
+
+    #[test]
+    fn name_traits() {
+        let names: HashSet<Name<u32, Abc>> = [3, 1, 3].into_iter().map(Name::new).collect();
+        assert_eq!(names.len(), 2);
+        assert!(Name::<u32, Abc>::new(1) < Name::new(2));
+        assert_eq!(format!("{:?}", Name::<u32, Abc>::new(3)), "Name(aa, 3)");
+    }
+}
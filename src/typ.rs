@@ -1,30 +1,298 @@
+use ::alloc::string::String;
+use ::alloc::string::ToString;
+use ::alloc::vec::Vec;
+use ::core::fmt;
+
 use crate::Charset;
 
+/// More variants may be added in minor versions.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum N2NErr {
     EmptyInput,
-    TooLarge { charset: Charset },
-    InvalidCharacter { character: char, charset: Charset },
+    /// The name is for a number larger than `max`, the largest number of the target type
+    /// (after mapping signed numbers to unsigned), whose name is `charset.encode_u128(max)`.
+    /// There is no charset for schemes that alternate between charsets.
+    TooLarge {
+        charset: Option<Charset>,
+        max: u128,
+    },
+    /// The first character that is not allowed, with its position in characters and bytes.
+    /// Any other invalid characters after it are in `more` (which only allocates if there are any).
+    InvalidCharacter {
+        character: char,
+        position: usize,
+        byte_offset: usize,
+        charset: Charset,
+        more: Vec<InvalidChar>,
+    },
+    InvalidLength {
+        expected: usize,
+        actual: usize,
+    },
+    /// Separators of a grouped name are missing or in the wrong place (when parsing strictly),
+    /// first at this position in characters and bytes.
+    InvalidGrouping {
+        position: usize,
+        byte_offset: usize,
+    },
+    /// The text does not start with the prefix of the id.
+    InvalidPrefix {
+        expected: &'static str,
+    },
+    /// The charset has a single character, but this needs at least two (like fixed-width ids).
+    SingleCharacter {
+        charset: Charset,
+    },
+}
+
+/// A character that is not allowed, and where it is in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidChar {
+    pub character: char,
+    /// Index in characters from the start of the input.
+    pub position: usize,
+    /// Index in bytes from the start of the input.
+    pub byte_offset: usize,
 }
 
 impl N2NErr {
     pub fn as_string(&self) -> String {
+        self.to_string()
+    }
+
+    /// All invalid characters in the input, in order (empty for other errors).
+    pub fn invalid_characters(&self) -> Vec<InvalidChar> {
+        match self {
+            N2NErr::InvalidCharacter {
+                character,
+                position,
+                byte_offset,
+                more,
+                ..
+            } => {
+                let mut all = Vec::with_capacity(1 + more.len());
+                all.push(InvalidChar {
+                    character: *character,
+                    position: *position,
+                    byte_offset: *byte_offset,
+                });
+                all.extend_from_slice(more);
+                all
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Find all characters of the text that are not valid (given their character position).
+    /// Should only be called if there is at least one.
+    pub(crate) fn invalid_in(
+        text: &str,
+        charset: &Charset,
+        mut is_valid: impl FnMut(usize, char) -> bool,
+    ) -> N2NErr {
+        let mut invalid = text
+            .char_indices()
+            .enumerate()
+            .filter(|&(position, (_, character))| !is_valid(position, character))
+            .map(|(position, (byte_offset, character))| InvalidChar {
+                character,
+                position,
+                byte_offset,
+            });
+        let first = invalid
+            .next()
+            .expect("no invalid character found for InvalidCharacter error");
+        N2NErr::InvalidCharacter {
+            character: first.character,
+            position: first.position,
+            byte_offset: first.byte_offset,
+            charset: charset.clone(),
+            more: invalid.collect(),
+        }
+    }
+
+    /// Move the positions of invalid characters, for input that started after this many characters and bytes.
+    pub(crate) fn with_offset(self, chars: usize, bytes: usize) -> N2NErr {
+        match self {
+            N2NErr::InvalidCharacter {
+                character,
+                position,
+                byte_offset,
+                charset,
+                mut more,
+            } => {
+                for invalid in &mut more {
+                    invalid.position += chars;
+                    invalid.byte_offset += bytes;
+                }
+                N2NErr::InvalidCharacter {
+                    character,
+                    position: position + chars,
+                    byte_offset: byte_offset + bytes,
+                    charset,
+                    more,
+                }
+            }
+            N2NErr::InvalidGrouping {
+                position,
+                byte_offset,
+            } => N2NErr::InvalidGrouping {
+                position: position + chars,
+                byte_offset: byte_offset + bytes,
+            },
+            other => other,
+        }
+    }
+
+    /// Use `max` as the largest number, e.g. when decoding to a narrower type.
+    pub(crate) fn with_max(self, max: u128) -> N2NErr {
+        match self {
+            N2NErr::TooLarge { charset, .. } => N2NErr::TooLarge { charset, max },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for N2NErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            N2NErr::EmptyInput => "input was empty while decoding".to_owned(),
-            N2NErr::TooLarge { charset } => format!(
-                "input was too long to decode number; maximum: {}",
-                charset.encode(std::u64::MAX)
+            N2NErr::EmptyInput => write!(f, "input was empty while decoding"),
+            N2NErr::TooLarge {
+                charset: Some(charset),
+                max,
+            } => write!(
+                f,
+                "input was too large to decode; maximum: {} (for number {})",
+                charset.display(*max),
+                max
+            ),
+            N2NErr::TooLarge { charset: None, max } => write!(
+                f,
+                "input was too large to decode; maximum is for number {}",
+                max
+            ),
+            N2NErr::InvalidCharacter {
+                character,
+                position,
+                charset,
+                more,
+                ..
+            } => {
+                write!(
+                    f,
+                    "encountered invalid character '{}' at position {}",
+                    character, position
+                )?;
+                if !more.is_empty() {
+                    write!(f, " (and {} more:", more.len())?;
+                    for invalid in more {
+                        write!(f, " '{}' at {}", invalid.character, invalid.position)?;
+                    }
+                    write!(f, ")")?;
+                }
+                write!(f, " while decoding to number; allowed: '{}'", charset)
+            }
+            N2NErr::InvalidLength { expected, actual } => write!(
+                f,
+                "input had {} characters while decoding, but expected {}",
+                actual, expected
             ),
-            N2NErr::InvalidCharacter { character, charset } => format!(
-                "encountered invalid character '{}' while decoding to number; allowed: '{}'",
-                character, charset
+            N2NErr::InvalidGrouping { position, .. } => write!(
+                f,
+                "separator missing or misplaced at position {} while decoding grouped name",
+                position
+            ),
+            N2NErr::InvalidPrefix { expected } => {
+                write!(f, "input did not start with '{}' while decoding", expected)
+            }
+            N2NErr::SingleCharacter { charset } => write!(
+                f,
+                "charset '{}' has a single character, but at least two are needed",
+                charset
             ),
         }
     }
 }
 
+impl ::core::error::Error for N2NErr {}
+
+#[allow(clippy::from_over_into)]
 impl Into<String> for N2NErr {
     fn into(self) -> String {
         self.as_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{name2number_u128, name2number_u16};
+
+    #[test]
+    fn all_invalid_characters() {
+        let charset = Charset::case_sensitive("abc");
+        let err = name2number_u16("aé-bd", &charset).unwrap_err();
+        let invalid = err.invalid_characters();
+        assert_eq!(
+            invalid,
+            [
+                InvalidChar {
+                    character: 'é',
+                    position: 1,
+                    byte_offset: 1
+                },
+                InvalidChar {
+                    character: '-',
+                    position: 2,
+                    byte_offset: 3
+                },
+                InvalidChar {
+                    character: 'd',
+                    position: 4,
+                    byte_offset: 5
+                },
+            ]
+        );
+        assert_eq!(
+            err.to_string(),
+            "encountered invalid character 'é' at position 1 (and 2 more: '-' at 2 'd' at 4) \
+             while decoding to number; allowed: 'abc'"
+        );
+    }
+
+    #[test]
+    fn maximum_of_type() {
+        let charset = Charset::case_sensitive("abc");
+        let name = charset.encode_int(u16::MAX as u32 + 1);
+        match name2number_u16(&name, &charset).unwrap_err() {
+            N2NErr::TooLarge { max, .. } => assert_eq!(max, u16::MAX as u128),
+            _ => panic!("wrong error"),
+        }
+        let err = charset
+            .decode_int::<i8>(charset.encode_int(1000u16))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "input was too large to decode; maximum: {} (for number 255)",
+                charset.encode_int(255u8)
+            )
+        );
+        match name2number_u128("c".repeat(100), &charset).unwrap_err() {
+            N2NErr::TooLarge { max, .. } => assert_eq!(max, u128::MAX),
+            _ => panic!("wrong error"),
+        }
+    }
+
+    #[test]
+    fn is_error() {
+        fn source(err: &dyn ::core::error::Error) -> String {
+            err.to_string()
+        }
+        assert_eq!(
+            source(&N2NErr::EmptyInput),
+            "input was empty while decoding"
+        );
+    }
+}
@@ -1,41 +1,96 @@
-/// Return the lowercase representation character.
-/// Fails if the lowercase representation is not a single character.
-pub fn lower(character: char) -> char {
+/// The lowercase representation, or None if it is not a single character.
+pub fn try_lower(character: char) -> Option<char> {
     let mut lc = character.to_lowercase();
     if lc.len() > 1 {
-        panic!(
-            "character {} is not allowed because its lower-case has more \
-        than one unicode code point ({})",
-            character, lc
-        );
+        return None;
     }
-    match lc.next() {
-        Some(lc_char) => lc_char,
-        None => panic!("no lower-case representation found"),
+    lc.next()
+}
+
+/// The character positions where the text without surrounding whitespace starts and ends.
+pub fn trimmed_range(text: &str) -> (usize, usize) {
+    let start = text.chars().take_while(|c| c.is_whitespace()).count();
+    let end = start + text.trim().chars().count();
+    (start, end)
+}
+
+/// The characters of the text at compile time; panics if there are not exactly `N`.
+#[cfg(any(feature = "builtin-charsets", test))]
+pub const fn chars_of<const N: usize>(text: &str) -> [char; N] {
+    let bytes = text.as_bytes();
+    let mut chars = ['\0'; N];
+    let mut count = 0;
+    let mut start = 0;
+    while start < bytes.len() {
+        // The text is valid UTF-8, so only the length and payload bits need to be read.
+        let (length, mut code) = match bytes[start] {
+            first @ 0x00..=0x7F => (1, first as u32),
+            first @ 0xC0..=0xDF => (2, first as u32 & 0x1F),
+            first @ 0xE0..=0xEF => (3, first as u32 & 0x0F),
+            first => (4, first as u32 & 0x07),
+        };
+        let mut index = 1;
+        while index < length {
+            code = (code << 6) | (bytes[start + index] as u32 & 0x3F);
+            index += 1;
+        }
+        assert!(count < N, "text has more characters than expected");
+        chars[count] = match char::from_u32(code) {
+            Some(character) => character,
+            None => panic!("text is not valid UTF-8"),
+        };
+        count += 1;
+        start += length;
     }
+    assert!(count == N, "text has fewer characters than expected");
+    chars
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_trimmed_range() {
+        assert_eq!(trimmed_range(" \tab c\n"), (2, 6));
+        assert_eq!(trimmed_range("  "), (2, 2));
+    }
+
+    #[test]
+    fn test_chars_of() {
+        assert_eq!(chars_of::<4>("aé中😀"), ['a', 'é', '中', '😀']);
+        assert_eq!(chars_of::<0>(""), []);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_chars_of_wrong_count() {
+        chars_of::<2>("abc");
+    }
+
     #[test]
     fn test_valid_lowercase() {
-        assert_eq!(lower('A'), 'a');
-        assert_eq!(lower('a'), 'a');
+        assert_eq!(try_lower('A'), Some('a'));
+        assert_eq!(try_lower('a'), Some('a'));
     }
 
     #[test]
     fn test_no_case() {
-        assert_eq!(lower('8'), '8');
-        assert_eq!(lower('+'), '+');
-        assert_eq!(lower('中'), '中');
+        assert_eq!(try_lower('8'), Some('8'));
+        assert_eq!(try_lower('+'), Some('+'));
+        assert_eq!(try_lower('中'), Some('中'));
     }
 
     #[test]
-    #[should_panic]
     fn test_invalid_lowercase() {
         // Example from https://doc.rust-lang.org/std/primitive.char.html#method.to_lowercase
-        lower('İ');
+        assert_eq!(try_lower('İ'), None);
+    }
+
+    #[test]
+    fn test_try_lower() {
+        assert_eq!(try_lower('A'), Some('a'));
+        assert_eq!(try_lower('中'), Some('中'));
+        assert_eq!(try_lower('İ'), None);
     }
 }